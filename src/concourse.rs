@@ -4,23 +4,197 @@
 use serde::{Deserialize, Serialize};
 
 use concourse_resource::IntoMetadataKV;
-use octocrab;
 use octocrab::models::IssueState;
+use octocrab::models::issues::IssueStateReason;
+use octocrab::models::reactions::ReactionContent;
 
 // standard concourse structs
 // check input and (vec seralized to list) output, out output
-#[derive(Eq, PartialEq, Serialize, Deserialize, Debug)]
+// concourse requires string values for versions, so number, updated, etag, comment, event, reaction, and reactions are strings
+#[derive(Eq, PartialEq, Serialize, Deserialize, Debug, Clone)]
 pub(super) struct Version {
     state: IssueState,
+    // optional for compatibility with versions output by earlier releases
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated: Option<String>,
+    // etag of the issue response for conditional requests during the next check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    // comment id for comment triggers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    // slash command and its arguments for command triggers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    arguments: Option<String>,
+    // event id, type, and actor login for event triggers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    actor: Option<String>,
+    // id of the reaction which reached the threshold (absent when the count dropped below it), and the reaction count for reaction triggers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reaction: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reactions: Option<String>,
 }
 
 impl Version {
     /// Constructor
     /// ```
-    /// let version = Version::new(IssueState::Closed);
+    /// let version = Version::new(IssueState::Closed, 5, "2024-01-01T00:00:00+00:00");
+    /// ```
+    pub(super) fn new(state: IssueState, number: u64, updated: impl Into<String>) -> Self {
+        Version {
+            state,
+            number: Some(number.to_string()),
+            updated: Some(updated.into()),
+            etag: None,
+            comment: None,
+            command: None,
+            arguments: None,
+            event: None,
+            event_type: None,
+            actor: None,
+            reaction: None,
+            reactions: None,
+        }
+    }
+
+    /// Attaches the etag of the issue response to the version.
+    /// ```
+    /// let version = Version::from(&issue).with_etag(Some(String::from("\"abc123\"")));
+    /// ```
+    pub(super) fn with_etag(self, etag: Option<String>) -> Self {
+        Version { etag, ..self }
+    }
+
+    /// Attaches the comment id to the version for comment triggers.
     /// ```
-    pub(super) fn new(state: IssueState) -> Self {
-        Version { state }
+    /// let version = Version::new(IssueState::Open, 5, "2024-01-01T00:00:00+00:00").with_comment(12345);
+    /// ```
+    pub(super) fn with_comment(self, comment: u64) -> Self {
+        Version {
+            comment: Some(comment.to_string()),
+            ..self
+        }
+    }
+
+    /// Attaches the slash command and its arguments to the version for command triggers.
+    /// ```
+    /// let version = Version::new(IssueState::Open, 5, "2024-01-01T00:00:00+00:00").with_comment(12345).with_command("deploy", "staging");
+    /// ```
+    pub(super) fn with_command(
+        self,
+        command: impl Into<String>,
+        arguments: impl Into<String>,
+    ) -> Self {
+        Version {
+            command: Some(command.into()),
+            arguments: Some(arguments.into()),
+            ..self
+        }
+    }
+
+    /// Attaches the event id, type, and actor login to the version for event triggers.
+    /// ```
+    /// let version = Version::new(IssueState::Open, 5, "2024-01-01T00:00:00+00:00").with_event(67890, "labeled", Some(String::from("octocat")));
+    /// ```
+    pub(super) fn with_event(
+        self,
+        event: u64,
+        event_type: impl Into<String>,
+        actor: Option<String>,
+    ) -> Self {
+        Version {
+            event: Some(event.to_string()),
+            event_type: Some(event_type.into()),
+            actor,
+            ..self
+        }
+    }
+
+    /// Attaches the id of the reaction which reached the threshold (or none when the count dropped below the threshold), and the reaction count, to the version for reaction triggers.
+    /// ```
+    /// let version = Version::new(IssueState::Open, 5, "2024-01-01T00:00:00+00:00").with_reactions(Some(13579), 3);
+    /// ```
+    pub(super) fn with_reactions(self, reaction: Option<u64>, reactions: usize) -> Self {
+        Version {
+            reaction: reaction.map(|reaction| reaction.to_string()),
+            reactions: Some(reactions.to_string()),
+            ..self
+        }
+    }
+
+    /// Readers
+    pub(super) fn number(&self) -> Option<u64> {
+        self.number
+            .as_deref()
+            .and_then(|number| number.parse().ok())
+    }
+    pub(super) fn updated(&self) -> Option<&str> {
+        self.updated.as_deref()
+    }
+    pub(super) fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+    pub(super) fn comment(&self) -> Option<u64> {
+        self.comment
+            .as_deref()
+            .and_then(|comment| comment.parse().ok())
+    }
+    pub(super) fn command(&self) -> Option<&str> {
+        self.command.as_deref()
+    }
+    pub(super) fn arguments(&self) -> Option<&str> {
+        self.arguments.as_deref()
+    }
+    pub(super) fn event(&self) -> Option<u64> {
+        self.event.as_deref().and_then(|event| event.parse().ok())
+    }
+    pub(super) fn reaction(&self) -> Option<u64> {
+        self.reaction
+            .as_deref()
+            .and_then(|reaction| reaction.parse().ok())
+    }
+    pub(super) fn reactions(&self) -> Option<usize> {
+        self.reactions
+            .as_deref()
+            .and_then(|reactions| reactions.parse().ok())
+    }
+
+    /// Determines whether the version occurred after the input previous version (i.e. cursor).
+    /// ```
+    /// if version.is_newer(&previous) {}
+    /// ```
+    pub(super) fn is_newer(&self, previous: &Version) -> bool {
+        // a different issue is always newer
+        if self.number != previous.number {
+            return true;
+        }
+        // rfc3339 timestamps in utc are chronologically ordered as strings
+        match (&self.updated, &previous.updated) {
+            (Some(updated), Some(previous_updated)) => updated > previous_updated,
+            // previous version from earlier releases without a cursor
+            _ => true,
+        }
+    }
+}
+
+impl From<&octocrab::models::issues::Issue> for Version {
+    fn from(issue: &octocrab::models::issues::Issue) -> Self {
+        // the closure time is unique to each closing of the issue, and otherwise the creation time is used so that edits to an open issue do not produce versions
+        // the check step instead uses the latest reopening time for a reopened issue
+        let updated = match issue.state {
+            IssueState::Closed => issue.closed_at.unwrap_or(issue.updated_at),
+            _ => issue.created_at,
+        };
+        Version::new(issue.state.clone(), issue.number, updated.to_rfc3339())
     }
 }
 
@@ -31,6 +205,17 @@ pub(super) struct Source {
     pat: Option<String>,
     owner: String,
     repo: String,
+    // github enterprise server
+    api_url: Option<String>,
+    // github app installation instead of pat
+    app_id: Option<u64>,
+    private_key: Option<String>,
+    installation_id: Option<u64>,
+    // retries for transient failures and rate limits
+    max_retries: Option<u32>,
+    max_retry_wait: Option<u64>,
+    // rate limit quota reserved for other steps during check
+    min_rate_limit_remaining: Option<usize>,
     // read
     number: Option<u64>,
     // list
@@ -39,10 +224,31 @@ pub(super) struct Source {
     assignee: Option<String>,
     creator: Option<String>,
     labels: Option<Vec<String>>,
+    per_page: Option<u8>,
+    max_issues: Option<usize>,
     // for skipping check during e.g. put+create
     skip_check: Option<bool>,
+    // for a version per issue from list during check
+    multiple: Option<bool>,
+    // for a version per new comment on the issue during check, optionally filtered by author and body regex
+    comments: Option<bool>,
+    comment_author: Option<String>,
+    comment_pattern: Option<String>,
+    // for a version per new slash command comment (e.g. /deploy staging) from a user with write permission during check
+    commands: Option<Vec<String>>,
+    // for a version per new issue event of the types (e.g. labeled) during check, optionally filtered by label name
+    events: Option<Vec<String>>,
+    event_label: Option<String>,
+    // for a version when the count of the reaction content (optionally from team members) reaches the threshold during check
+    reaction: Option<ReactionContent>,
+    reaction_threshold: Option<usize>,
+    reaction_team: Option<String>,
     // trigger on issue state open or closed
     trigger: Option<IssueState>,
+    // and optionally the reason for the issue state e.g. completed or not_planned
+    trigger_state_reason: Option<IssueStateReason>,
+    // or compound conditions instead of the above trigger
+    conditions: Option<Conditions>,
 }
 
 impl Source {
@@ -56,6 +262,27 @@ impl Source {
     pub(super) fn repo(&self) -> &str {
         &self.repo
     }
+    pub(super) fn api_url(&self) -> Option<&str> {
+        self.api_url.as_deref()
+    }
+    pub(super) fn app_id(&self) -> Option<u64> {
+        self.app_id
+    }
+    pub(super) fn private_key(&self) -> Option<&str> {
+        self.private_key.as_deref()
+    }
+    pub(super) fn installation_id(&self) -> Option<u64> {
+        self.installation_id
+    }
+    pub(super) fn max_retries(&self) -> Option<u32> {
+        self.max_retries
+    }
+    pub(super) fn max_retry_wait(&self) -> Option<u64> {
+        self.max_retry_wait
+    }
+    pub(super) fn min_rate_limit_remaining(&self) -> Option<usize> {
+        self.min_rate_limit_remaining
+    }
     pub(super) fn number(&self) -> Option<u64> {
         self.number
     }
@@ -67,10 +294,7 @@ impl Source {
     }
     pub(super) fn assignee(&self) -> Option<Vec<String>> {
         // convert assignee to single element string vector for compatibility with github issue constructor
-        match self.assignee.clone() {
-            Some(assignee) => Some(vec![assignee]),
-            None => None,
-        }
+        self.assignee.clone().map(|assignee| vec![assignee])
     }
     pub(super) fn creator(&self) -> Option<&str> {
         self.creator.as_deref()
@@ -78,14 +302,160 @@ impl Source {
     pub(super) fn labels(&self) -> Option<Vec<String>> {
         self.labels.clone()
     }
+    pub(super) fn per_page(&self) -> Option<u8> {
+        self.per_page
+    }
+    pub(super) fn max_issues(&self) -> Option<usize> {
+        self.max_issues
+    }
     // return unwrapped value with default false for ease of use
     pub(super) fn skip_check(&self) -> bool {
         self.skip_check.unwrap_or(false)
     }
+    // return unwrapped value with default false for ease of use
+    pub(super) fn multiple(&self) -> bool {
+        self.multiple.unwrap_or(false)
+    }
+    pub(super) fn comments(&self) -> bool {
+        self.comments.unwrap_or(false)
+    }
+    pub(super) fn comment_author(&self) -> Option<&str> {
+        self.comment_author.as_deref()
+    }
+    pub(super) fn comment_pattern(&self) -> Option<&str> {
+        self.comment_pattern.as_deref()
+    }
+    pub(super) fn commands(&self) -> Option<&[String]> {
+        self.commands.as_deref()
+    }
+    pub(super) fn events(&self) -> Option<&[String]> {
+        self.events.as_deref()
+    }
+    pub(super) fn event_label(&self) -> Option<&str> {
+        self.event_label.as_deref()
+    }
+    pub(super) fn reaction(&self) -> Option<&ReactionContent> {
+        self.reaction.as_ref()
+    }
+    // return unwrapped value with default 1 for ease of use
+    pub(super) fn reaction_threshold(&self) -> usize {
+        self.reaction_threshold.unwrap_or(1)
+    }
+    pub(super) fn reaction_team(&self) -> Option<&str> {
+        self.reaction_team.as_deref()
+    }
     // return unwrapped value with default closed for ease of use
     pub(super) fn trigger(&self) -> IssueState {
         self.trigger.clone().unwrap_or(IssueState::Closed)
     }
+    pub(super) fn trigger_state_reason(&self) -> Option<&IssueStateReason> {
+        self.trigger_state_reason.as_ref()
+    }
+    pub(super) fn conditions(&self) -> Option<&Conditions> {
+        self.conditions.as_ref()
+    }
+}
+
+// compound trigger conditions which must all match the issue
+#[derive(Eq, PartialEq, Deserialize, Debug, Default)]
+pub(super) struct Conditions {
+    state: Option<IssueState>,
+    state_reason: Option<IssueStateReason>,
+    // the issue must have all of the labels and assignees
+    labels: Option<Vec<String>>,
+    assignees: Option<Vec<String>>,
+    milestone: Option<u64>,
+    // regular expressions which the title and body must match
+    title_pattern: Option<String>,
+    body_pattern: Option<String>,
+}
+
+impl Conditions {
+    /// Validates and compiles the regular expressions in the conditions once for matching any number of issues.
+    /// ```
+    /// let conditions = conditions.compile()?;
+    /// ```
+    pub(super) fn compile(&self) -> Result<CompiledConditions<'_>, regex::Error> {
+        let compile =
+            |pattern: &Option<String>| pattern.as_deref().map(regex::Regex::new).transpose();
+        Ok(CompiledConditions {
+            conditions: self,
+            title_pattern: compile(&self.title_pattern)?,
+            body_pattern: compile(&self.body_pattern)?,
+        })
+    }
+}
+
+// compound trigger conditions with their compiled regular expressions
+#[derive(Debug)]
+pub(super) struct CompiledConditions<'conditions> {
+    conditions: &'conditions Conditions,
+    title_pattern: Option<regex::Regex>,
+    body_pattern: Option<regex::Regex>,
+}
+
+impl CompiledConditions<'_> {
+    /// Determines whether the issue matches all of the conditions.
+    /// ```
+    /// if conditions.matches(&issue) {}
+    /// ```
+    pub(super) fn matches(&self, issue: &octocrab::models::issues::Issue) -> bool {
+        // github label names and logins are case insensitive
+        let has_label = |label: &String| {
+            issue
+                .labels
+                .iter()
+                .any(|issue_label| issue_label.name.eq_ignore_ascii_case(label))
+        };
+        let has_assignee = |assignee: &String| {
+            issue
+                .assignees
+                .iter()
+                .any(|issue_assignee| issue_assignee.login.eq_ignore_ascii_case(assignee))
+        };
+        let is_match = |pattern: &Option<regex::Regex>, text: &str| {
+            pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(text))
+        };
+        let conditions = self.conditions;
+
+        conditions
+            .state
+            .as_ref()
+            .is_none_or(|state| issue.state == *state)
+            && conditions
+                .state_reason
+                .as_ref()
+                .is_none_or(|state_reason| issue.state_reason.as_ref() == Some(state_reason))
+            && conditions
+                .labels
+                .as_ref()
+                .is_none_or(|labels| labels.iter().all(has_label))
+            && conditions
+                .assignees
+                .as_ref()
+                .is_none_or(|assignees| assignees.iter().all(has_assignee))
+            && conditions.milestone.is_none_or(|milestone| {
+                issue
+                    .milestone
+                    .as_ref()
+                    .is_some_and(|issue_milestone| issue_milestone.number == milestone as i64)
+            })
+            && is_match(&self.title_pattern, &issue.title)
+            && is_match(
+                &self.body_pattern,
+                issue.body.as_deref().unwrap_or_default(),
+            )
+    }
+}
+
+// lock as either a boolean, or a lock reason which implies locking
+#[derive(Eq, PartialEq, Deserialize, Debug)]
+#[serde(untagged)]
+pub(super) enum Lock {
+    Locked(bool),
+    Reason(String),
 }
 
 // out input
@@ -98,9 +468,27 @@ pub(super) struct OutParams {
     assignees: Option<Vec<String>>,
     milestone: Option<u64>,
     // update only
+    number: Option<u64>,
     comment: Option<String>,
-    lock: Option<bool>,
+    lock: Option<Lock>,
     state: Option<String>,
+    state_reason: Option<String>,
+    // individual labels instead of replacing all labels
+    add_labels: Option<Vec<String>>,
+    remove_labels: Option<Vec<String>>,
+    // individual assignees instead of replacing all assignees
+    add_assignees: Option<Vec<String>>,
+    remove_assignees: Option<Vec<String>>,
+    // verify assignees can be assigned before create or update
+    validate_assignees: Option<bool>,
+    // files relative to input path instead of title, body, and comment
+    title_file: Option<String>,
+    body_file: Option<String>,
+    comment_file: Option<String>,
+    number_file: Option<String>,
+    // update matching open issue instead of create
+    upsert: Option<bool>,
+    dedupe_key: Option<String>,
 }
 
 impl OutParams {
@@ -120,15 +508,66 @@ impl OutParams {
     pub(super) fn milestone(&self) -> Option<u64> {
         self.milestone
     }
+    pub(super) fn number(&self) -> Option<u64> {
+        self.number
+    }
     pub(super) fn comment(&self) -> Option<&str> {
         self.comment.as_deref()
     }
+    // a lock reason implies locking
     pub(super) fn lock(&self) -> Option<bool> {
-        self.lock
+        match self.lock {
+            Some(Lock::Locked(locked)) => Some(locked),
+            Some(Lock::Reason(_)) => Some(true),
+            None => None,
+        }
+    }
+    pub(super) fn lock_reason(&self) -> Option<&str> {
+        match &self.lock {
+            Some(Lock::Reason(reason)) => Some(reason),
+            _ => None,
+        }
     }
     pub(super) fn state(&self) -> Option<&str> {
         self.state.as_deref()
     }
+    pub(super) fn state_reason(&self) -> Option<&str> {
+        self.state_reason.as_deref()
+    }
+    pub(super) fn add_labels(&self) -> Option<Vec<String>> {
+        self.add_labels.clone()
+    }
+    pub(super) fn remove_labels(&self) -> Option<Vec<String>> {
+        self.remove_labels.clone()
+    }
+    pub(super) fn add_assignees(&self) -> Option<Vec<String>> {
+        self.add_assignees.clone()
+    }
+    pub(super) fn remove_assignees(&self) -> Option<Vec<String>> {
+        self.remove_assignees.clone()
+    }
+    pub(super) fn title_file(&self) -> Option<&str> {
+        self.title_file.as_deref()
+    }
+    pub(super) fn body_file(&self) -> Option<&str> {
+        self.body_file.as_deref()
+    }
+    pub(super) fn comment_file(&self) -> Option<&str> {
+        self.comment_file.as_deref()
+    }
+    pub(super) fn number_file(&self) -> Option<&str> {
+        self.number_file.as_deref()
+    }
+    // return unwrapped value with default false for ease of use
+    pub(super) fn upsert(&self) -> bool {
+        self.upsert.unwrap_or(false)
+    }
+    pub(super) fn validate_assignees(&self) -> bool {
+        self.validate_assignees.unwrap_or(false)
+    }
+    pub(super) fn dedupe_key(&self) -> Option<&str> {
+        self.dedupe_key.as_deref()
+    }
 }
 
 // out output
@@ -138,6 +577,8 @@ pub(super) struct OutMetadata {
     url: String,
     title: String,
     state: IssueState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state_reason: Option<IssueStateReason>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     labels: Vec<octocrab::models::Label>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -156,6 +597,7 @@ impl OutMetadata {
     ///     String::from("http://does.not.exist"),
     ///     String::from("some issue"),
     ///     IssueState::Open,
+    ///     None,
     ///     vec![],
     ///     vec![],
     ///     None,
@@ -163,12 +605,14 @@ impl OutMetadata {
     ///     String::from("today"),
     /// ),
     /// ```
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
         // ref: https://docs.rs/octocrab/latest/octocrab/models/issues/struct.Issue.html
         number: u64,
         url: impl Into<String>,
         title: String,
         state: IssueState,
+        state_reason: Option<IssueStateReason>,
         labels: Vec<octocrab::models::Label>,
         assignees: Vec<octocrab::models::Author>,
         milestone: Option<octocrab::models::Milestone>,
@@ -183,6 +627,7 @@ impl OutMetadata {
             url,
             title,
             state,
+            state_reason,
             labels,
             assignees,
             milestone,
@@ -192,5 +637,8 @@ impl OutMetadata {
     }
 }
 
+// in output is identical to out output
+pub(super) type InMetadata = OutMetadata;
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,140 @@
+//! # Error
+//!
+//! `error` contains the errors for the concourse steps, and their exit codes and guidance for reporting to concourse.
+use crate::github_issue::IssueError;
+
+// errors for the concourse steps
+#[derive(Debug)]
+pub(super) enum StepError {
+    // invalid source or params
+    Config(String),
+    // github issue interactions; the context describes the step operation that failed
+    Issue { context: String, source: IssueError },
+}
+
+impl StepError {
+    /// Constructor for github issue interaction errors.
+    /// ```
+    /// let error = StepError::issue("the check step was unable to return a github issue", issue_error);
+    /// ```
+    pub(super) fn issue(context: impl Into<String>, source: IssueError) -> Self {
+        StepError::Issue {
+            context: context.into(),
+            source,
+        }
+    }
+
+    /// Returns the distinct process exit code for the error classification.
+    /// ```
+    /// std::process::exit(error.exit_code());
+    /// ```
+    pub(super) fn exit_code(&self) -> i32 {
+        match self {
+            StepError::Config(_) => 2,
+            StepError::Issue { source, .. } => match source {
+                IssueError::Config(_) => 2,
+                IssueError::NotFound { .. } => 3,
+                IssueError::Unauthorized { .. } => 4,
+                IssueError::Forbidden { .. } => 5,
+                IssueError::RateLimited { .. } | IssueError::QuotaReserved(_) => 6,
+                IssueError::Validation { .. } => 7,
+                IssueError::Server { .. } | IssueError::Network { .. } => 8,
+                IssueError::Rejected { .. } => 9,
+                IssueError::Other { .. } => 1,
+            },
+        }
+    }
+
+    // guidance for resolving the error according to its classification
+    fn guidance(&self) -> Option<&'static str> {
+        match self {
+            StepError::Config(_) => None,
+            StepError::Issue { source, .. } => match source {
+                IssueError::Config(_) => None,
+                IssueError::NotFound { .. } => Some(
+                    "verify that the owner, repo, and issue number exist, and are visible to the credentials",
+                ),
+                IssueError::Unauthorized { .. } => Some(
+                    "verify that the personal access token or github app credentials are valid and unexpired",
+                ),
+                IssueError::Forbidden { .. } => Some(
+                    "verify that the credentials are permitted to read and write issues in the repository",
+                ),
+                IssueError::RateLimited { .. } => Some(
+                    "the github api rate limit was exceeded, and the step should be retried after the limit resets",
+                ),
+                IssueError::QuotaReserved(_) => Some(
+                    "the github api rate limit remaining is below 'min_rate_limit_remaining', and the step should be retried after the limit resets",
+                ),
+                IssueError::Validation { .. } => Some(
+                    "verify that the params values (e.g. labels, assignees, milestone, state) are valid for the repository",
+                ),
+                IssueError::Server { .. } | IssueError::Network { .. } => {
+                    Some("github may be unreachable or unavailable, and the step should be retried")
+                }
+                IssueError::Rejected { .. } => Some(
+                    "github rejected the request (e.g. conflict or gone), and the step will fail again without changes to the source, params, or issue",
+                ),
+                IssueError::Other { .. } => {
+                    Some("this is an unexpected error, and should be reported")
+                }
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for StepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StepError::Config(message) => write!(f, "{message}")?,
+            StepError::Issue { context, source } => write!(f, "{context}: {source}")?,
+        }
+        match self.guidance() {
+            Some(guidance) => write!(f, "\n{guidance}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for StepError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StepError::Config(_) => None,
+            StepError::Issue { source, .. } => Some(source),
+        }
+    }
+}
+
+// errors are equal if their classifications and messages are equal (issue errors are compared by classification)
+impl PartialEq for StepError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (StepError::Config(message), StepError::Config(other_message)) => {
+                message == other_message
+            }
+            (
+                StepError::Issue { context, source },
+                StepError::Issue {
+                    context: other_context,
+                    source: other_source,
+                },
+            ) => context == other_context && source == other_source,
+            _ => false,
+        }
+    }
+}
+
+impl From<&str> for StepError {
+    fn from(message: &str) -> Self {
+        StepError::Config(String::from(message))
+    }
+}
+
+impl From<String> for StepError {
+    fn from(message: String) -> Self {
+        StepError::Config(message)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,90 @@
+use super::*;
+
+#[test]
+fn test_step_error_exit_code() {
+    // validates distinct exit codes for error classifications
+    assert_eq!(
+        StepError::from("source is required for the Github Issue resource").exit_code(),
+        2,
+        "configuration step error did not return the configuration exit code",
+    );
+    assert_eq!(
+        StepError::issue(
+            "the check step was unable to return a github issue from the source values",
+            IssueError::Config("issue number unspecified"),
+        )
+        .exit_code(),
+        2,
+        "issue configuration step error did not return the configuration exit code",
+    );
+    assert_eq!(
+        StepError::issue(
+            "the check step was unable to return a github issue from the source values",
+            IssueError::QuotaReserved("rate limit quota reserved"),
+        )
+        .exit_code(),
+        6,
+        "reserved rate limit quota step error did not return the rate limit exit code",
+    );
+    assert_eq!(
+        StepError::issue(
+            "the out/put step was unable to Update the associated github issue",
+            IssueError::Rejected {
+                message: "issue not updated",
+                source: octocrab::Error::Installation {
+                    backtrace: std::backtrace::Backtrace::capture(),
+                },
+            },
+        )
+        .exit_code(),
+        9,
+        "rejected issue step error did not return the rejected exit code",
+    );
+    assert_eq!(
+        StepError::issue(
+            "the out/put step was unable to Create the associated github issue",
+            IssueError::new(
+                "issue not created",
+                octocrab::Error::Installation {
+                    backtrace: std::backtrace::Backtrace::capture(),
+                },
+            ),
+        )
+        .exit_code(),
+        1,
+        "unexpected issue step error did not return the general exit code",
+    );
+}
+
+#[test]
+fn test_step_error_display() {
+    // validates display of context, issue error, and guidance
+    assert_eq!(
+        StepError::from(String::from("params is required")).to_string(),
+        "params is required",
+        "configuration step error did not display with message",
+    );
+    assert_eq!(
+        StepError::issue(
+            "the check step was unable to return a github issue from the source values",
+            IssueError::Config("issue number unspecified"),
+        )
+        .to_string(),
+        "the check step was unable to return a github issue from the source values: issue number unspecified",
+        "issue configuration step error did not display with context and message",
+    );
+    assert_eq!(
+        StepError::issue(
+            "the out/put step was unable to Create the associated github issue",
+            IssueError::new(
+                "issue not created",
+                octocrab::Error::Installation {
+                    backtrace: std::backtrace::Backtrace::capture(),
+                },
+            ),
+        )
+        .to_string(),
+        "the out/put step was unable to Create the associated github issue: issue not created: octocrab failure\nthis is an unexpected error, and should be reported",
+        "issue step error did not display with context, message, classification, and guidance",
+    );
+}
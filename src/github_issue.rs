@@ -1,10 +1,8 @@
 //! # Github Issue
 //!
 //! `github_issue` is a minimal utility to create and update issues within Github.
-use log;
-
-use octocrab;
 use octocrab::models::IssueState;
+use octocrab::models::issues::IssueStateReason;
 use octocrab::params::LockReason;
 use octocrab::params::State;
 
@@ -23,8 +21,160 @@ impl From<Action> for String {
     }
 }
 
+// errors for github issue interactions
+// the message describes the failed interaction, and the source is the octocrab error that caused it
+#[derive(Debug)]
+pub(super) enum IssueError {
+    // invalid configuration detected before or without interacting with github
+    Config(&'static str),
+    // rate limit remaining below the minimum, so no further interactions were attempted
+    QuotaReserved(&'static str),
+    NotFound {
+        message: &'static str,
+        source: octocrab::Error,
+    },
+    Unauthorized {
+        message: &'static str,
+        source: octocrab::Error,
+    },
+    Forbidden {
+        message: &'static str,
+        source: octocrab::Error,
+    },
+    RateLimited {
+        message: &'static str,
+        source: octocrab::Error,
+    },
+    Validation {
+        message: &'static str,
+        source: octocrab::Error,
+    },
+    // any other client error status e.g. 400, 409, 410, which is never retried
+    Rejected {
+        message: &'static str,
+        source: octocrab::Error,
+    },
+    // server error status (5xx)
+    Server {
+        message: &'static str,
+        source: octocrab::Error,
+    },
+    Network {
+        message: &'static str,
+        source: octocrab::Error,
+    },
+    // any other octocrab error e.g. deserialization
+    Other {
+        message: &'static str,
+        source: octocrab::Error,
+    },
+}
+
+impl IssueError {
+    /// Constructor that classifies the octocrab error by its cause.
+    /// ```
+    /// let error = IssueError::new("unknown issue", octocrab_error);
+    /// ```
+    pub(super) fn new(message: &'static str, source: octocrab::Error) -> Self {
+        // github response status, and whether the response is a rate limit
+        let status = match &source {
+            octocrab::Error::GitHub {
+                source: github_error,
+                ..
+            } => Some((
+                github_error.status_code.as_u16(),
+                github_error.message.to_lowercase().contains("rate limit"),
+            )),
+            _ => None,
+        };
+        match status {
+            Some((401, _)) => IssueError::Unauthorized { message, source },
+            Some((403, true) | (429, _)) => IssueError::RateLimited { message, source },
+            Some((403, false)) => IssueError::Forbidden { message, source },
+            Some((404, _)) => IssueError::NotFound { message, source },
+            Some((422, _)) => IssueError::Validation { message, source },
+            Some((400..=499, _)) => IssueError::Rejected { message, source },
+            Some((500..=599, _)) => IssueError::Server { message, source },
+            Some(_) => IssueError::Other { message, source },
+            None => match source {
+                octocrab::Error::Hyper { .. }
+                | octocrab::Error::Service { .. }
+                | octocrab::Error::Http { .. } => IssueError::Network { message, source },
+                _ => IssueError::Other { message, source },
+            },
+        }
+    }
+
+    /// Readers
+    pub(super) fn message(&self) -> &'static str {
+        match self {
+            IssueError::Config(message)
+            | IssueError::QuotaReserved(message)
+            | IssueError::NotFound { message, .. }
+            | IssueError::Unauthorized { message, .. }
+            | IssueError::Forbidden { message, .. }
+            | IssueError::RateLimited { message, .. }
+            | IssueError::Validation { message, .. }
+            | IssueError::Rejected { message, .. }
+            | IssueError::Server { message, .. }
+            | IssueError::Network { message, .. }
+            | IssueError::Other { message, .. } => message,
+        }
+    }
+}
+
+impl std::fmt::Display for IssueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cause = match self {
+            IssueError::Config(_) => return write!(f, "{}", self.message()),
+            IssueError::QuotaReserved(_) => "rate limit quota reserved",
+            IssueError::NotFound { .. } => "not found",
+            IssueError::Unauthorized { .. } => "unauthorized",
+            IssueError::Forbidden { .. } => "forbidden",
+            IssueError::RateLimited { .. } => "rate limited",
+            IssueError::Validation { .. } => "validation failed",
+            IssueError::Rejected { .. } => "rejected by github",
+            IssueError::Server { .. } => "unexpected github response",
+            IssueError::Network { .. } => "network failure",
+            IssueError::Other { .. } => "octocrab failure",
+        };
+        write!(f, "{}: {cause}", self.message())
+    }
+}
+
+impl std::error::Error for IssueError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IssueError::Config(_) | IssueError::QuotaReserved(_) => None,
+            IssueError::NotFound { source, .. }
+            | IssueError::Unauthorized { source, .. }
+            | IssueError::Forbidden { source, .. }
+            | IssueError::RateLimited { source, .. }
+            | IssueError::Validation { source, .. }
+            | IssueError::Rejected { source, .. }
+            | IssueError::Server { source, .. }
+            | IssueError::Network { source, .. }
+            | IssueError::Other { source, .. } => Some(source),
+        }
+    }
+}
+
+// errors are equal if their classifications and messages are equal (octocrab errors do not implement PartialEq)
+impl PartialEq for IssueError {
+    fn eq(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+            && self.message() == other.message()
+    }
+}
+
+impl From<&'static str> for IssueError {
+    fn from(message: &'static str) -> Self {
+        IssueError::Config(message)
+    }
+}
+
 // convert string to IssueState or params::State without trait implementations because not allowed (no impl Into<IssueState> for &str)
-fn str_to_issue_state(param: &str) -> Result<IssueState, &str> {
+fn str_to_issue_state(param: &str) -> Result<IssueState, &'static str> {
     match param {
         "open" => Ok(IssueState::Open),
         "closed" => Ok(IssueState::Closed),
@@ -39,7 +189,7 @@ fn str_to_issue_state(param: &str) -> Result<IssueState, &str> {
     }
 }
 
-fn str_to_params_state(param: &str) -> Result<State, &str> {
+fn str_to_params_state(param: &str) -> Result<State, &'static str> {
     match param {
         "open" => Ok(State::Open),
         "closed" => Ok(State::Closed),
@@ -48,169 +198,878 @@ fn str_to_params_state(param: &str) -> Result<State, &str> {
     }
 }
 
-// struct for general interfacing with module
-// the types correspond to octocrab when not advantageous otherwise
-#[derive(Eq, PartialEq, Debug)]
-pub(super) struct Issue<'issue> {
+fn str_to_state_reason(param: &str) -> Result<IssueStateReason, &'static str> {
+    match param {
+        "completed" => Ok(IssueStateReason::Completed),
+        "not_planned" => Ok(IssueStateReason::NotPlanned),
+        "reopened" => Ok(IssueStateReason::Reopened),
+        "duplicate" => Ok(IssueStateReason::Duplicate),
+        &_ => Err(
+            "the issue state reason must be either completed, not_planned, reopened, or duplicate",
+        ),
+    }
+}
+
+fn str_to_lock_reason(param: &str) -> Result<LockReason, &'static str> {
+    match param {
+        "off-topic" => Ok(LockReason::OffTopic),
+        "too heated" => Ok(LockReason::TooHeated),
+        "resolved" => Ok(LockReason::Resolved),
+        "spam" => Ok(LockReason::Spam),
+        &_ => Err("the lock reason must be either off-topic, too heated, resolved, or spam"),
+    }
+}
+
+// the legacy permission maps maintain to write, but the role name is also checked in case the legacy permission is absent
+fn is_writer(permission: &str, role_name: &str) -> bool {
+    matches!(permission, "admin" | "write") || matches!(role_name, "admin" | "maintain" | "write")
+}
+
+// collaborator permission response deserialized directly because the octocrab model does not accept the legacy "write" permission
+#[derive(serde::Deserialize)]
+struct CollaboratorPermission {
+    #[serde(default)]
+    permission: String,
+    #[serde(default)]
+    role_name: String,
+}
+
+// issue event deserialized directly because the octocrab timeline event model rejects unknown event types
+#[derive(serde::Deserialize, Debug)]
+pub(super) struct IssueEvent {
+    pub(super) id: u64,
+    pub(super) event: String,
+    // absent for deleted users
+    pub(super) actor: Option<EventActor>,
+    pub(super) created_at: chrono::DateTime<chrono::Utc>,
+    // only for labeled and unlabeled events
+    pub(super) label: Option<EventLabel>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub(super) struct EventActor {
+    pub(super) login: String,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub(super) struct EventLabel {
+    pub(super) name: String,
+}
+
+// client configuration shared by all interactions within a step
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub(super) struct ClientConfig<'issue> {
     // client and issues: OctocrabBuilder and issues::IssueHandler
-    pat: Option<&'issue str>,
-    owner: &'issue str,
-    repo: &'issue str,
-    // create and update (octocrab update expects AsRef<str> instead of String and AsRef<[String]> instead of Vec<String>)
-    title: Option<&'issue str>,
-    body: Option<&'issue str>,
-    labels: Option<Vec<String>>,
+    pub(super) pat: Option<&'issue str>,
+    pub(super) owner: &'issue str,
+    pub(super) repo: &'issue str,
+    // github enterprise server
+    pub(super) api_url: Option<&'issue str>,
+    // github app installation instead of pat
+    pub(super) app_id: Option<u64>,
+    pub(super) private_key: Option<&'issue str>,
+    pub(super) installation_id: Option<u64>,
+    // retries for transient failures and rate limits
+    pub(super) max_retries: Option<u32>,
+    pub(super) max_retry_wait: Option<u64>,
+    // rate limit quota reserved for other interactions
+    pub(super) min_rate_limit_remaining: Option<usize>,
+}
+
+impl ClientConfig<'_> {
+    // instantiate an octocrab client with input authentication and api url
+    fn octocrab(&self) -> Result<octocrab::Octocrab, IssueError> {
+        // builder for github enterprise server api url if specified, and otherwise github api url
+        // octocrab request retries are disabled because retries are handled with backoff for the entire interaction
+        let builder = || {
+            let builder = octocrab::Octocrab::builder()
+                .add_retry_config(octocrab::service::middleware::retry::RetryConfig::None);
+            match self.api_url {
+                Some(api_url) => builder.base_uri(api_url),
+                None => Ok(builder),
+            }
+        };
+        if let Err(error) = builder() {
+            log::error!(
+                "the api url {} is invalid",
+                self.api_url.unwrap_or_default()
+            );
+            log::error!("{error}");
+            return Err(IssueError::Config("invalid api url"));
+        }
+        match (
+            self.pat,
+            self.app_id,
+            self.private_key,
+            self.installation_id,
+        ) {
+            // github app installation
+            (None, Some(app_id), Some(private_key), Some(installation_id)) => {
+                let key = match jsonwebtoken::EncodingKey::from_rsa_pem(private_key.as_bytes()) {
+                    Ok(key) => key,
+                    Err(error) => {
+                        log::error!("the github app private key is not a valid rsa pem key");
+                        log::error!("{error}");
+                        return Err(IssueError::Config("invalid private key"));
+                    }
+                };
+                // the installation client mints and refreshes installation tokens on demand
+                match builder()
+                    .and_then(|builder| builder.app(app_id.into(), key).build())
+                    .and_then(|client| client.installation(installation_id.into()))
+                {
+                    Ok(client) => Ok(client),
+                    Err(error) => {
+                        log::error!(
+                            "could not authenticate client as github app {app_id} installation {installation_id}"
+                        );
+                        log::error!("{error}");
+                        Err(IssueError::new("github app authentication failed", error))
+                    }
+                }
+            }
+            (Some(_), Some(_), _, _) | (Some(_), _, Some(_), _) | (Some(_), _, _, Some(_)) => {
+                log::error!(
+                    "both a personal access token and github app credentials were specified"
+                );
+                Err(IssueError::Config("multiple authentication methods"))
+            }
+            (None, None, None, None) => match builder().and_then(|builder| builder.build()) {
+                Ok(client) => Ok(client),
+                Err(error) => {
+                    log::error!("could not build the unauthenticated client");
+                    log::error!("{error}");
+                    Err(IssueError::new("client not built", error))
+                }
+            },
+            (None, _, _, _) => {
+                log::error!(
+                    "the app_id, private_key, and installation_id are all required for github app authentication"
+                );
+                Err(IssueError::Config("incomplete github app credentials"))
+            }
+            (Some(pat), None, None, None) => {
+                match builder().and_then(|builder| builder.personal_token(pat).build()) {
+                    Ok(client) => Ok(client),
+                    Err(error) => {
+                        log::error!("could not authenticate client with Personal Access Token");
+                        log::error!("{error}");
+                        Err(IssueError::new("authentication failed", error))
+                    }
+                }
+            }
+        }
+    }
+}
+
+// rate limit reported by the headers of a github response
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+struct RateLimit {
+    remaining: Option<usize>,
+    limit: Option<usize>,
+    // utc epoch seconds
+    reset: Option<i64>,
+    // seconds to wait before retrying e.g. after a secondary rate limit
+    retry_after: Option<u64>,
+}
+
+impl RateLimit {
+    // parse the rate limit headers, which are absent from e.g. github enterprise server responses without rate limiting
+    fn from_headers(headers: &http::HeaderMap) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+        };
+        Self {
+            remaining: header("x-ratelimit-remaining").and_then(|value| value.parse().ok()),
+            limit: header("x-ratelimit-limit").and_then(|value| value.parse().ok()),
+            reset: header("x-ratelimit-reset").and_then(|value| value.parse().ok()),
+            retry_after: header("retry-after").and_then(|value| value.parse().ok()),
+        }
+    }
+}
+
+// github client built once per step and shared by all interactions, which records the rate limit of each response
+pub(super) struct Client<'issue> {
+    octocrab: octocrab::Octocrab,
+    config: ClientConfig<'issue>,
+    // rate limit of the latest response
+    rate_limit: std::sync::Mutex<RateLimit>,
+}
+
+impl<'issue> Client<'issue> {
+    /// Constructor for the client with the authentication and api url of the configuration. Verifies the authentication and reports the rate limit quota.
+    /// ```
+    /// let client = Client::new(config).await?;
+    /// ```
+    pub(super) async fn new(config: ClientConfig<'issue>) -> Result<Self, IssueError> {
+        let client = Self {
+            octocrab: config.octocrab()?,
+            config,
+            rate_limit: std::sync::Mutex::default(),
+        };
+        log::debug!("built octocrab client");
+        client.retry(async || client.verify().await).await?;
+
+        Ok(client)
+    }
+
+    // verify the authentication, and reserve the rate limit quota for other interactions if specified
+    async fn verify(&self) -> Result<(), IssueError> {
+        // verify authentication with the rate limit endpoint because it is free and accessible with all authentication methods
+        let authenticated = self.config.pat.is_some() || self.config.app_id.is_some();
+        let rate = match self
+            .fetch::<octocrab::models::RateLimit, ()>(http::Method::GET, "/rate_limit", None)
+            .await
+        {
+            Ok(rate_limit) => {
+                if authenticated {
+                    log::debug!("verified octocrab client authentication");
+                }
+                rate_limit.resources.core
+            }
+            // github enterprise server without rate limiting responds not found, and so the quota is unknown
+            Err(octocrab::Error::GitHub { source, .. })
+                if source.status_code == http::StatusCode::NOT_FOUND =>
+            {
+                log::info!("the github rate limit is not enabled for the api url");
+                log::debug!("{}", source.message);
+                if self.config.min_rate_limit_remaining.is_some() {
+                    log::warn!("the minimum rate limit remaining cannot be reserved");
+                }
+                if authenticated {
+                    self.verify_authentication().await?;
+                }
+                return Ok(());
+            }
+            // unauthorized for invalid, expired, or revoked credentials
+            Err(error) if authenticated => {
+                log::error!("the github authentication could not be verified");
+                log::error!("{error}");
+                return Err(IssueError::new("authentication failed", error));
+            }
+            // the quota is unknown, but the interactions may still succeed
+            Err(error) => {
+                log::warn!("the github rate limit could not be retrieved");
+                log::warn!("{error}");
+                return Ok(());
+            }
+        };
+        // the remaining quota of the rate limit endpoint itself is logged with its response headers
+        if let Some(min_rate_limit_remaining) = self.config.min_rate_limit_remaining
+            && rate.remaining < min_rate_limit_remaining
+        {
+            log::warn!(
+                "the github rate limit remaining {} is below the minimum {min_rate_limit_remaining}",
+                rate.remaining
+            );
+            return Err(IssueError::QuotaReserved("rate limit quota reserved"));
+        }
+
+        Ok(())
+    }
+
+    // verify the authentication with an endpoint which is accessible to the authentication method
+    async fn verify_authentication(&self) -> Result<(), IssueError> {
+        // installation tokens cannot access the authenticated user
+        let route = match self.config.app_id {
+            Some(_) => "/installation/repositories?per_page=1",
+            None => "/user",
+        };
+        match self
+            .fetch::<serde::de::IgnoredAny, ()>(http::Method::GET, route, None)
+            .await
+        {
+            Ok(_) => {
+                log::debug!("verified octocrab client authentication");
+                Ok(())
+            }
+            Err(error) => {
+                log::error!("the github authentication could not be verified");
+                log::error!("{error}");
+                Err(IssueError::new("authentication failed", error))
+            }
+        }
+    }
+
+    // route to the repository of the configuration
+    fn repo_route(&self) -> String {
+        format!("/repos/{}/{}", self.config.owner, self.config.repo)
+    }
+
+    // send the request, record the rate limit of the response, and return the response value with its etag
+    // the value is none for unmodified (304) and no content (204) responses
+    async fn request<T: octocrab::FromResponse, B: serde::Serialize + ?Sized>(
+        &self,
+        method: http::Method,
+        uri: &str,
+        headers: http::HeaderMap,
+        body: Option<&B>,
+    ) -> octocrab::Result<octocrab::etag::Etagged<T>> {
+        let mut builder = http::Request::builder().method(method).uri(uri);
+        if let Some(request_headers) = builder.headers_mut() {
+            request_headers.extend(headers);
+        }
+        let request = self.octocrab.build_request(builder, body)?;
+        let response = self.octocrab.execute(request).await?;
+        // octocrab does not expose the headers of responses, so the rate limit is recorded from the raw response
+        let response_rate_limit = RateLimit::from_headers(response.headers());
+        if let RateLimit {
+            remaining: Some(remaining),
+            limit: Some(limit),
+            reset,
+            ..
+        } = response_rate_limit
+        {
+            let reset = reset
+                .and_then(|reset| chrono::DateTime::from_timestamp(reset, 0))
+                .map(|reset| reset.to_rfc3339())
+                .unwrap_or_default();
+            log::info!(
+                "the github rate limit has {remaining} of {limit} requests remaining until {reset}"
+            );
+        }
+        if let Ok(mut rate_limit) = self.rate_limit.lock() {
+            *rate_limit = response_rate_limit;
+        }
+        if matches!(
+            response.status(),
+            http::StatusCode::NOT_MODIFIED | http::StatusCode::NO_CONTENT
+        ) {
+            return Ok(octocrab::etag::Etagged {
+                etag: None,
+                value: None,
+            });
+        }
+        let response = octocrab::map_github_error(response).await?;
+        let etag = octocrab::etag::EntityTag::extract_from_response(&response);
+
+        Ok(octocrab::etag::Etagged {
+            etag,
+            value: Some(T::from_response(response).await?),
+        })
+    }
+
+    // send the request, and return the response value which must be present
+    async fn fetch<T: octocrab::FromResponse, B: serde::Serialize + ?Sized>(
+        &self,
+        method: http::Method,
+        uri: &str,
+        body: Option<&B>,
+    ) -> octocrab::Result<T> {
+        self.request(method, uri, http::HeaderMap::new(), body)
+            .await?
+            .value
+            .ok_or_else(|| octocrab::Error::Other {
+                source: format!("the response to {uri} is unexpectedly empty").into(),
+                backtrace: std::backtrace::Backtrace::capture(),
+            })
+    }
+
+    // send the single write, and return the response value if any
+    // the write is only retried when it was certainly not applied
+    async fn write<T: octocrab::FromResponse, B: serde::Serialize + ?Sized>(
+        &self,
+        message: &'static str,
+        method: http::Method,
+        uri: &str,
+        body: Option<&B>,
+    ) -> Result<Option<T>, IssueError> {
+        self.retry_write(async || {
+            self.request(method.clone(), uri, http::HeaderMap::new(), body)
+                .await
+                .map(|etagged| etagged.value)
+                .map_err(|error| IssueError::new(message, error))
+        })
+        .await
+    }
+
+    // request the pages of the list until exhausted or the maximum number of items is reached
+    async fn list<T: serde::de::DeserializeOwned>(
+        &self,
+        route: String,
+        max_items: Option<usize>,
+    ) -> octocrab::Result<Vec<T>> {
+        let mut items = Vec::new();
+        let mut next_route = Some(route);
+        while let Some(route) = next_route {
+            let page = self
+                .fetch::<octocrab::Page<T>, ()>(http::Method::GET, &route, None)
+                .await?;
+            items.extend(page.items);
+            if let Some(max_items) = max_items
+                && items.len() >= max_items
+            {
+                log::info!("the maximum number of items {max_items} was reached during listing");
+                items.truncate(max_items);
+                break;
+            }
+            // the next page link is absolute, and only its path is requested so that the authentication is also sent to github enterprise server
+            next_route = page
+                .next
+                .as_ref()
+                .and_then(http::Uri::path_and_query)
+                .map(ToString::to_string);
+            if next_route.is_some() {
+                log::debug!("listing next page");
+            }
+        }
+
+        Ok(items)
+    }
+
+    // execute the idempotent operation, and retry it for transient failures and rate limits until the maximum number of retries
+    async fn retry<T>(
+        &self,
+        operation: impl AsyncFn() -> Result<T, IssueError>,
+    ) -> Result<T, IssueError> {
+        self.retry_operation(operation, true).await
+    }
+
+    // execute the single write, and retry it only for failures which guarantee the write was not applied
+    // e.g. a timeout after the request was sent could otherwise duplicate a comment
+    async fn retry_write<T>(
+        &self,
+        operation: impl AsyncFn() -> Result<T, IssueError>,
+    ) -> Result<T, IssueError> {
+        self.retry_operation(operation, false).await
+    }
+
+    async fn retry_operation<T>(
+        &self,
+        operation: impl AsyncFn() -> Result<T, IssueError>,
+        idempotent: bool,
+    ) -> Result<T, IssueError> {
+        let max_retries = self.config.max_retries.unwrap_or(3);
+        let mut attempt = 0;
+        loop {
+            self.reserve_quota()?;
+            let error = match operation().await {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+            if attempt >= max_retries {
+                if max_retries > 0 {
+                    log::error!("the github interaction failed after {max_retries} retries");
+                }
+                return Err(error);
+            }
+            let Some(wait) = self.retry_wait(&error, attempt, idempotent) else {
+                return Err(error);
+            };
+            attempt += 1;
+            log::warn!("{error}");
+            log::warn!(
+                "retrying the github interaction ({attempt} of {max_retries}) in {:.1} seconds",
+                wait.as_secs_f64()
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    // reserve the rate limit quota for other interactions once the remaining quota of the latest response is below the minimum
+    fn reserve_quota(&self) -> Result<(), IssueError> {
+        let remaining = self
+            .rate_limit
+            .lock()
+            .ok()
+            .and_then(|rate_limit| rate_limit.remaining);
+        if let (Some(min_rate_limit_remaining), Some(remaining)) =
+            (self.config.min_rate_limit_remaining, remaining)
+            && remaining < min_rate_limit_remaining
+        {
+            log::warn!(
+                "the github rate limit remaining {remaining} is below the minimum {min_rate_limit_remaining}"
+            );
+            return Err(IssueError::QuotaReserved("rate limit quota reserved"));
+        }
+
+        Ok(())
+    }
+
+    // duration to wait before retrying after the error, or none if the error is not retryable or the wait would exceed the maximum
+    // non-idempotent operations are only retried for errors which guarantee the request was not applied
+    fn retry_wait(
+        &self,
+        error: &IssueError,
+        attempt: u32,
+        idempotent: bool,
+    ) -> Option<std::time::Duration> {
+        let max_wait = std::time::Duration::from_secs(self.config.max_retry_wait.unwrap_or(60));
+        // exponential backoff from one second with jitter to avoid concurrent steps retrying in lockstep
+        let backoff = std::time::Duration::from_secs(1 << attempt.min(16))
+            .min(max_wait)
+            .mul_f64(rand::Rng::gen_range(&mut rand::thread_rng(), 0.5..=1.0));
+        match error {
+            IssueError::Server { .. } if idempotent => Some(backoff),
+            IssueError::Network { .. } if idempotent || is_unsent(error) => Some(backoff),
+            // rate limited requests are rejected before they are applied
+            IssueError::RateLimited { .. } => {
+                // the retry-after and primary rate limit reset (x-ratelimit-reset) are recorded from the rate limited response
+                let rate_limit = self
+                    .rate_limit
+                    .lock()
+                    .map(|rate_limit| *rate_limit)
+                    .unwrap_or_default();
+                let reset = match rate_limit {
+                    RateLimit {
+                        retry_after: Some(retry_after),
+                        ..
+                    } => Some(std::time::Duration::from_secs(retry_after)),
+                    RateLimit {
+                        remaining: Some(0),
+                        reset: Some(reset),
+                        ..
+                    } => {
+                        let reset = reset - chrono::Utc::now().timestamp();
+                        Some(std::time::Duration::from_secs(reset.max(1) as u64))
+                    }
+                    _ => None,
+                };
+                // otherwise this is a secondary rate limit without a retry-after, and github specifies waiting at least one minute
+                let wait = reset
+                    .unwrap_or(std::time::Duration::from_secs(60))
+                    .max(backoff);
+                if wait > max_wait {
+                    log::error!(
+                        "the rate limit resets in {} seconds, which exceeds the maximum retry wait of {} seconds",
+                        wait.as_secs(),
+                        max_wait.as_secs()
+                    );
+                    None
+                } else {
+                    Some(wait)
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+// whether the error guarantees the request was never sent e.g. the connection was refused
+fn is_unsent(error: &IssueError) -> bool {
+    let mut source = std::error::Error::source(error);
+    while let Some(error) = source {
+        if let Some(io_error) = error.downcast_ref::<std::io::Error>()
+            && io_error.kind() == std::io::ErrorKind::ConnectionRefused
+        {
+            return true;
+        }
+        source = error.source();
+    }
+
+    false
+}
+
+// parameters for the individual interactions with the issues of the client repository
+// the types correspond to octocrab when not advantageous otherwise, and unspecified parameters default to none
+#[derive(Eq, PartialEq, Debug, Default)]
+pub(super) struct Issue<'issue> {
+    // create and update
+    pub(super) title: Option<&'issue str>,
+    pub(super) body: Option<&'issue str>,
+    pub(super) labels: Option<Vec<String>>,
     // also list in addition to above comment considerations
-    assignees: Option<Vec<String>>,
+    pub(super) assignees: Option<Vec<String>>,
     // list
-    creator: Option<&'issue str>,
+    pub(super) creator: Option<&'issue str>,
+    pub(super) since: Option<&'issue str>,
+    pub(super) per_page: Option<u8>,
+    pub(super) max_issues: Option<usize>,
     // read and update
-    number: Option<u64>,
+    pub(super) number: Option<u64>,
+    // conditional read
+    pub(super) etag: Option<&'issue str>,
     // update
-    comment: Option<&'issue str>,
-    lock: Option<bool>,
+    pub(super) comment: Option<&'issue str>,
+    pub(super) lock: Option<bool>,
+    pub(super) lock_reason: Option<&'issue str>,
+    // individual labels instead of replacing all labels
+    pub(super) add_labels: Option<Vec<String>>,
+    pub(super) remove_labels: Option<Vec<String>>,
+    // individual assignees instead of replacing all assignees
+    pub(super) add_assignees: Option<Vec<String>>,
+    pub(super) remove_assignees: Option<Vec<String>>,
+    // create and update
+    pub(super) validate_assignees: bool,
     // update IssueState and list State
-    state: Option<&'issue str>,
+    pub(super) state: Option<&'issue str>,
+    // update
+    pub(super) state_reason: Option<&'issue str>,
     // create, list, and update
-    milestone: Option<u64>,
+    pub(super) milestone: Option<u64>,
 }
 
-impl<'issue> Issue<'issue> {
-    /// Constructor for the Config struct. Contains all of the members necessary for instantiating a client and performing an action.
-    /// ```
-    /// let gh_issue = Issue::new(None, String::from("my_org"), String::from("my_repo"), None, None, None, None, None, Some(100), None, None, None, None);
-    /// ```
-    pub(super) fn new(
-        pat: Option<&'issue str>,
-        owner: &'issue str,
-        repo: &'issue str,
-        title: Option<&'issue str>,
-        body: Option<&'issue str>,
-        labels: Option<Vec<String>>,
-        assignees: Option<Vec<String>>,
-        creator: Option<&'issue str>,
-        number: Option<u64>,
-        comment: Option<&'issue str>,
-        lock: Option<bool>,
-        state: Option<&'issue str>,
-        milestone: Option<u64>,
-    ) -> Self {
-        // return instantiated github issue
-        Self {
-            pat,
-            owner,
-            repo,
-            title,
-            body,
-            labels,
-            assignees,
-            creator,
-            number,
-            comment,
-            lock,
-            state,
-            milestone,
-        }
-    }
-
-    /// Instantiate a reusable Octocrab issues object with input authentication, and an input owner and repo.
+// percent-encode the value for a route path segment or query parameter
+fn encode(value: &str) -> String {
+    percent_encoding::utf8_percent_encode(value, percent_encoding::NON_ALPHANUMERIC).to_string()
+}
+
+// append the specified query parameters to the route
+fn with_query(route: String, parameters: &[(&str, Option<String>)]) -> String {
+    let query = parameters
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .as_ref()
+                .map(|value| format!("{name}={}", encode(value)))
+        })
+        .collect::<Vec<String>>()
+        .join("&");
+    match query.is_empty() {
+        true => route,
+        false => format!("{route}?{query}"),
+    }
+}
+
+impl Issue<'_> {
+    /// Create, list, read, or update an issue in the repository of the client.
+    /// Transient failures and rate limits are retried with exponential backoff for reads, and each individual write is only retried when it was certainly not applied.
     /// ```
-    /// let issue = gh_issue.main(Action::Read).await?;
+    /// let issue = gh_issue.main(&client, Action::Read).await?;
     /// ```
-    pub(super) async fn main<'octo>(
+    pub(super) async fn main(
         &self,
+        client: &Client<'_>,
         action: Action,
-    ) -> Result<octocrab::models::issues::Issue, &str> {
-        // instantiate client and issues
-        let client = match &self.pat {
-            Some(pat) => octocrab::Octocrab::builder()
-                .personal_token(*pat)
-                .build()
-                .unwrap_or_else(|_| {
-                    log::warn!("could not authenticate client with Personal Access Token");
-                    log::warn!("will continue with unauthenticated client");
-                    octocrab::Octocrab::default()
-                }),
-            None => octocrab::Octocrab::default(),
-        };
-        log::debug!("built octocrab client");
-        let issues = client.issues(self.owner, self.repo);
-        log::debug!("built octocrab issues");
+    ) -> Result<octocrab::models::issues::Issue, IssueError> {
+        // validate parameters before any interaction so that invalid values do not cause a partial update
+        self.validate()?;
+        // validate assignees before the first write for the same reason
+        if matches!(action, Action::Create | Action::Update) {
+            for assignees in [&self.assignees, &self.add_assignees].into_iter().flatten() {
+                self.validate_assignees(client, assignees).await?;
+            }
+        }
         // execute action and assign returned issue
         let issue = match action {
             // create an issue
-            Action::Create => self.create(issues).await?,
+            Action::Create => self.create(client).await,
             // list issues and filter to one issue
-            Action::List => self.list(issues).await?,
+            Action::List => client.retry(async || self.list(client).await).await,
             // read an issue state
-            Action::Read => self.read(issues).await?,
+            Action::Read => client.retry(async || self.read(client).await).await,
             // update an issue
-            Action::Update => self.update(issues).await?,
-        };
+            Action::Update => self.update(client).await,
+        }?;
         log::debug!("issue interfacing completed");
 
         Ok(issue)
     }
 
+    /// Return all issues from a list according to the filters.
+    /// ```
+    /// let issues = gh_issue.main_list(&client).await?;
+    /// ```
+    pub(super) async fn main_list(
+        &self,
+        client: &Client<'_>,
+    ) -> Result<Vec<octocrab::models::issues::Issue>, IssueError> {
+        let vec_issues = client
+            .retry(async || self.list_issues(client, self.max_issues).await)
+            .await?;
+        log::debug!("issue interfacing completed");
+
+        Ok(vec_issues)
+    }
+
+    /// Read the issue only if it was modified since the etag.
+    /// The value is none if the issue is unmodified, and github does not count this against the rate limit.
+    /// ```
+    /// let etagged = gh_issue.main_read_etagged(&client).await?;
+    /// ```
+    pub(super) async fn main_read_etagged(
+        &self,
+        client: &Client<'_>,
+    ) -> Result<octocrab::etag::Etagged<octocrab::models::issues::Issue>, IssueError> {
+        let etagged = client
+            .retry(async || self.read_etagged(client).await)
+            .await?;
+        log::debug!("issue interfacing completed");
+
+        Ok(etagged)
+    }
+
+    /// Return all comments on the issue created or updated since the timestamp.
+    /// ```
+    /// let comments = gh_issue.main_list_comments(&client).await?;
+    /// ```
+    pub(super) async fn main_list_comments(
+        &self,
+        client: &Client<'_>,
+    ) -> Result<Vec<octocrab::models::issues::Comment>, IssueError> {
+        let vec_comments = client
+            .retry(async || self.list_comments(client).await)
+            .await?;
+        log::debug!("issue interfacing completed");
+
+        Ok(vec_comments)
+    }
+
+    /// Return all events on the issue.
+    /// ```
+    /// let events = gh_issue.main_list_events(&client).await?;
+    /// ```
+    pub(super) async fn main_list_events(
+        &self,
+        client: &Client<'_>,
+    ) -> Result<Vec<IssueEvent>, IssueError> {
+        let vec_events = client
+            .retry(async || self.list_events(client).await)
+            .await?;
+        log::debug!("issue interfacing completed");
+
+        Ok(vec_events)
+    }
+
+    /// Return all reactions on the issue.
+    /// ```
+    /// let reactions = gh_issue.main_list_reactions(&client).await?;
+    /// ```
+    pub(super) async fn main_list_reactions(
+        &self,
+        client: &Client<'_>,
+    ) -> Result<Vec<octocrab::models::reactions::Reaction>, IssueError> {
+        let vec_reactions = client
+            .retry(async || self.list_reactions(client).await)
+            .await?;
+        log::debug!("issue interfacing completed");
+
+        Ok(vec_reactions)
+    }
+
+    /// Return the logins of all members of the team within the owner organization.
+    /// ```
+    /// let members = gh_issue.main_team_members(&client, "release-managers").await?;
+    /// ```
+    pub(super) async fn main_team_members(
+        &self,
+        client: &Client<'_>,
+        team: &str,
+    ) -> Result<Vec<String>, IssueError> {
+        let vec_members = client
+            .retry(async || self.list_team_members(client, team).await)
+            .await?;
+        log::debug!("issue interfacing completed");
+
+        Ok(vec_members)
+    }
+
+    /// Return the users with write, maintain, or admin permission on the repository.
+    /// ```
+    /// let writers = gh_issue.main_writers(&client, &["octocat"]).await?;
+    /// ```
+    pub(super) async fn main_writers(
+        &self,
+        client: &Client<'_>,
+        users: &[&str],
+    ) -> Result<Vec<String>, IssueError> {
+        // read the permission of each user
+        let mut writers = Vec::new();
+        for user in users {
+            if client
+                .retry(async || self.can_write(client, user).await)
+                .await?
+            {
+                writers.push(user.to_string());
+            }
+        }
+        log::debug!("issue interfacing completed");
+
+        Ok(writers)
+    }
+
+    /// Validate the parameters which are converted during the interactions, so that invalid values error before any interaction.
+    /// ```
+    /// gh_issue.validate()?;
+    /// ```
+    pub(super) fn validate(&self) -> Result<(), IssueError> {
+        if let Some(lock_reason) = self.lock_reason
+            && let Err(error) = str_to_lock_reason(lock_reason)
+        {
+            log::error!("the lock reason {lock_reason} is invalid");
+            return Err(IssueError::Config(error));
+        }
+        if let Some(state_reason) = self.state_reason
+            && let Err(error) = str_to_state_reason(state_reason)
+        {
+            log::error!("the state reason {state_reason} is invalid");
+            return Err(IssueError::Config(error));
+        }
+
+        Ok(())
+    }
+
     // create a github issue according to configuration
-    async fn create<'octo>(
+    async fn create(
         &self,
-        issues: octocrab::issues::IssueHandler<'octo>,
-    ) -> Result<octocrab::models::issues::Issue, &str> {
+        client: &Client<'_>,
+    ) -> Result<octocrab::models::issues::Issue, IssueError> {
         // validate a title was specified
         match self.title {
             // title specified
             Some(title) => {
                 // build the issue
-                // createissuebuilder milestone type is impl Into<Option<u64>> so we can build it immediately
-                let mut issue = issues.create(title);
+                let mut issue = serde_json::json!({ "title": title });
                 // ... with optional parameters
                 if let Some(body) = self.body {
-                    issue = issue.body(body);
+                    issue["body"] = body.into();
                 }
                 if let Some(labels) = &self.labels {
-                    issue = issue.labels(labels.clone());
+                    issue["labels"] = labels.clone().into();
                 }
                 if let Some(assignees) = &self.assignees {
-                    issue = issue.assignees(assignees.clone());
+                    issue["assignees"] = assignees.clone().into();
                 }
                 if let Some(milestone) = self.milestone {
-                    issue = issue.milestone(milestone);
+                    issue["milestone"] = milestone.into();
                 }
 
                 log::debug!("creating issue");
                 // send and await the issue
-                match issue.send().await {
+                let route = format!("{}/issues", client.repo_route());
+                match client
+                    .retry_write(async || {
+                        client
+                            .fetch(http::Method::POST, &route, Some(&issue))
+                            .await
+                            .map_err(|error| IssueError::new("issue not created", error))
+                    })
+                    .await
+                {
                     // return created issue
                     Ok(issue) => Ok(issue),
                     // issue could not be created
                     Err(error) => {
                         log::error!("the issue could not be created");
                         log::error!("{error}");
-                        return Err("issue not created");
+                        Err(error)
                     }
                 }
             }
             // title unspecified
             None => {
                 log::error!("a title was not specified, and so an issue could not be created");
-                return Err("title unspecified");
+                Err(IssueError::Config("title unspecified"))
             }
         }
     }
 
     // read a github issue according to configuration
-    async fn read<'octo>(
+    async fn read(
         &self,
-        issues: octocrab::issues::IssueHandler<'octo>,
-    ) -> Result<octocrab::models::issues::Issue, &str> {
+        client: &Client<'_>,
+    ) -> Result<octocrab::models::issues::Issue, IssueError> {
         // validate an issue number was specified
         match self.number {
             // issue number specified
             Some(number) => {
                 log::debug!("reading issue");
-                // retrieve the issue with the handler
-                match issues.get(number).await {
+                // retrieve the issue
+                let route = format!("{}/issues/{number}", client.repo_route());
+                match client.fetch::<_, ()>(http::Method::GET, &route, None).await {
                     Ok(issue) => Ok(issue),
                     // issue number probably does not exist, or some other error
                     Err(error) => {
@@ -218,7 +1077,7 @@ impl<'issue> Issue<'issue> {
                             "the issue number {number} could not be retrieved from the repository"
                         );
                         log::error!("{error}");
-                        return Err("unknown issue");
+                        Err(IssueError::new("unknown issue", error))
                     }
                 }
             }
@@ -227,153 +1086,535 @@ impl<'issue> Issue<'issue> {
                 log::error!(
                     "an issue number was not specified, and so its state cannot be retrieved"
                 );
-                return Err("issue number unspecified");
+                Err(IssueError::Config("issue number unspecified"))
             }
         }
     }
 
-    // list github issues according to configuration
-    async fn list<'octo>(
+    // read a github issue with a conditional request for the etag
+    async fn read_etagged(
         &self,
-        issues: octocrab::issues::IssueHandler<'octo>,
-    ) -> Result<octocrab::models::issues::Issue, &str> {
-        // build the issue pages
-        let mut issue_page = issues.list();
-        // ... with optional parameters
-        if let Some(creator) = &self.creator {
-            issue_page = issue_page.creator(*creator);
+        client: &Client<'_>,
+    ) -> Result<octocrab::etag::Etagged<octocrab::models::issues::Issue>, IssueError> {
+        // validate an issue number was specified
+        let Some(number) = self.number else {
+            log::error!("an issue number was not specified, and so its state cannot be retrieved");
+            return Err(IssueError::Config("issue number unspecified"));
+        };
+        // the request is sent with the if-none-match header for the etag
+        let mut headers = http::HeaderMap::new();
+        if let Some(etag) = self.etag {
+            match etag.parse::<octocrab::etag::EntityTag>() {
+                Ok(etag) => {
+                    if let Err(error) = octocrab::etag::EntityTag::insert_if_none_match_header(
+                        &mut headers,
+                        etag.clone(),
+                    ) {
+                        log::warn!(
+                            "the etag {etag} could not be sent, and so the issue will be read unconditionally"
+                        );
+                        log::warn!("{error}");
+                    }
+                }
+                Err(_) => log::warn!(
+                    "the etag {etag} is invalid, and so the issue will be read unconditionally"
+                ),
+            }
         }
-        if let Some(state) = self.state {
-            // convert str state to params state
-            let params_state = str_to_params_state(state)?;
-            issue_page = issue_page.state(params_state);
+        log::debug!("reading issue conditionally");
+        let route = format!("{}/issues/{number}", client.repo_route());
+        match client
+            .request::<_, ()>(http::Method::GET, &route, headers, None)
+            .await
+        {
+            // unmodified since the etag
+            Ok(octocrab::etag::Etagged { value: None, .. }) => {
+                log::debug!("the issue number {number} is unmodified since the etag");
+                Ok(octocrab::etag::Etagged {
+                    etag: self.etag.and_then(|etag| etag.parse().ok()),
+                    value: None,
+                })
+            }
+            Ok(etagged) => Ok(etagged),
+            // issue number probably does not exist, or some other error
+            Err(error) => {
+                log::error!("the issue number {number} could not be retrieved from the repository");
+                log::error!("{error}");
+                Err(IssueError::new("unknown issue", error))
+            }
+        }
+    }
+
+    // list github issues according to configuration, and filter to one issue
+    async fn list(
+        &self,
+        client: &Client<'_>,
+    ) -> Result<octocrab::models::issues::Issue, IssueError> {
+        // a second issue already means the list is ambiguous, and so the remaining pages are irrelevant
+        let vec_issues = self.list_issues(client, Some(2)).await?;
+        // ensure only one issue exists in the list
+        match vec_issues.len() {
+            1 => Ok(vec_issues[0].clone()),
+            _ => {
+                let num = vec_issues.len();
+                log::error!("expected only one issue to be returned from filtered list");
+                log::error!("actual number of issues returned was {num}");
+                Err(IssueError::Config("unexpected number of issues"))
+            }
         }
-        if let Some(milestone) = self.milestone {
-            issue_page = issue_page.milestone(milestone);
+    }
+
+    // list github issues according to configuration through all pages
+    async fn list_issues(
+        &self,
+        client: &Client<'_>,
+        max_issues: Option<usize>,
+    ) -> Result<Vec<octocrab::models::issues::Issue>, IssueError> {
+        // validate the state
+        if let Some(state) = self.state {
+            str_to_params_state(state)?;
         }
-        if let Some(assignees) = &self.assignees {
-            // assert only one assignee in assignees
-            let num_assignees = assignees.len();
-            if num_assignees != 1 {
+        // assert only one assignee in assignees
+        let assignee = match &self.assignees {
+            Some(assignees) if assignees.len() == 1 => Some(assignees[0].clone()),
+            Some(assignees) => {
+                let num_assignees = assignees.len();
                 log::error!("list action attempted with other than one assignee: {num_assignees}");
                 log::error!(
                     "this is an error with custom resource frontend and backend interfacing, and should be reported"
                 );
-                return Err("multiple assignees and list action");
+                return Err(IssueError::Config("multiple assignees and list action"));
             }
-            // assign value of only assignee and use for assignee filter
-            let assignee = &assignees[0][..];
-            issue_page = issue_page.assignee(assignee);
-        }
-        if let Some(labels) = &self.labels {
-            issue_page = issue_page.labels(labels);
-        }
+            None => None,
+        };
+        // build the issue list route with optional parameters
+        let route = with_query(
+            format!("{}/issues", client.repo_route()),
+            &[
+                ("creator", self.creator.map(String::from)),
+                ("since", self.since()?.map(|since| since.to_rfc3339())),
+                ("state", self.state.map(String::from)),
+                (
+                    "milestone",
+                    self.milestone.map(|milestone| milestone.to_string()),
+                ),
+                ("assignee", assignee),
+                (
+                    "labels",
+                    self.labels.as_ref().map(|labels| labels.join(",")),
+                ),
+                (
+                    "per_page",
+                    self.per_page.map(|per_page| per_page.to_string()),
+                ),
+            ],
+        );
 
         log::debug!("listing issues");
-        // send and await the issue page
-        let page = match issue_page.send().await {
-            // return issue pages
-            Ok(page) => page,
+        // follow next page links until exhausted or maximum number of issues reached
+        match client.list(route, max_issues).await {
+            Ok(vec_issues) => Ok(vec_issues),
             // issues probably do not exist with given filters, or some other error
             Err(error) => {
                 log::error!(
                     "the issues with the given filters could not be retrieved from the repository"
                 );
                 log::error!("{error}");
-                return Err("unknown issues");
+                Err(IssueError::new("unknown issues", error))
             }
+        }
+    }
+
+    // list the comments on a github issue according to configuration
+    async fn list_comments(
+        &self,
+        client: &Client<'_>,
+    ) -> Result<Vec<octocrab::models::issues::Comment>, IssueError> {
+        // validate an issue number was specified
+        let Some(number) = self.number else {
+            log::error!("an issue number was not specified, and so its comments cannot be listed");
+            return Err(IssueError::Config("issue number unspecified"));
         };
-        // items member is Page<T> into Vec<T> so we can iter
-        let vec_issues = page.items;
-        // ensure only one issue exists in octocrab::Page<octocrab::models::issues::Issue>
-        match vec_issues.len() {
-            1 => Ok(vec_issues[0].clone()),
-            _ => {
-                let num = vec_issues.len();
-                log::error!("expected only one issue to be returned from filtered list");
-                log::error!("actual number of issues returned was {num}");
-                return Err("unexpected number of issues");
+        // build the comment list route with optional parameters
+        let route = with_query(
+            format!("{}/issues/{number}/comments", client.repo_route()),
+            &[
+                ("since", self.since()?.map(|since| since.to_rfc3339())),
+                (
+                    "per_page",
+                    self.per_page.map(|per_page| per_page.to_string()),
+                ),
+            ],
+        );
+
+        log::debug!("listing comments");
+        // follow next page links until exhausted
+        match client.list(route, None).await {
+            Ok(vec_comments) => Ok(vec_comments),
+            // issue number probably does not exist, or some other error
+            Err(error) => {
+                log::error!("the comments on issue number {number} could not be retrieved");
+                log::error!("{error}");
+                Err(IssueError::new("unknown comments", error))
+            }
+        }
+    }
+
+    // list the events on a github issue according to configuration
+    async fn list_events(&self, client: &Client<'_>) -> Result<Vec<IssueEvent>, IssueError> {
+        // validate an issue number was specified
+        let Some(number) = self.number else {
+            log::error!("an issue number was not specified, and so its events cannot be listed");
+            return Err(IssueError::Config("issue number unspecified"));
+        };
+        // build the event list route with optional parameters
+        let route = with_query(
+            format!("{}/issues/{number}/events", client.repo_route()),
+            &[(
+                "per_page",
+                self.per_page.map(|per_page| per_page.to_string()),
+            )],
+        );
+
+        log::debug!("listing events");
+        // follow next page links until exhausted
+        match client.list(route, None).await {
+            Ok(vec_events) => Ok(vec_events),
+            // issue number probably does not exist, or some other error
+            Err(error) => {
+                log::error!("the events on issue number {number} could not be retrieved");
+                log::error!("{error}");
+                Err(IssueError::new("unknown events", error))
+            }
+        }
+    }
+
+    // list the reactions on a github issue according to configuration
+    async fn list_reactions(
+        &self,
+        client: &Client<'_>,
+    ) -> Result<Vec<octocrab::models::reactions::Reaction>, IssueError> {
+        // validate an issue number was specified
+        let Some(number) = self.number else {
+            log::error!("an issue number was not specified, and so its reactions cannot be listed");
+            return Err(IssueError::Config("issue number unspecified"));
+        };
+        // build the reaction list route with optional parameters
+        let route = with_query(
+            format!("{}/issues/{number}/reactions", client.repo_route()),
+            &[(
+                "per_page",
+                self.per_page.map(|per_page| per_page.to_string()),
+            )],
+        );
+
+        log::debug!("listing reactions");
+        // follow next page links until exhausted
+        match client.list(route, None).await {
+            Ok(vec_reactions) => Ok(vec_reactions),
+            // issue number probably does not exist, or some other error
+            Err(error) => {
+                log::error!("the reactions on issue number {number} could not be retrieved");
+                log::error!("{error}");
+                Err(IssueError::new("unknown reactions", error))
+            }
+        }
+    }
+
+    // list the logins of the members of a team within the owner organization
+    async fn list_team_members(
+        &self,
+        client: &Client<'_>,
+        team: &str,
+    ) -> Result<Vec<String>, IssueError> {
+        // build the member list route with optional parameters
+        let route = with_query(
+            format!(
+                "/orgs/{}/teams/{}/members",
+                client.config.owner,
+                encode(team)
+            ),
+            &[(
+                "per_page",
+                self.per_page.map(|per_page| per_page.to_string()),
+            )],
+        );
+
+        log::debug!("listing team members");
+        // follow next page links until exhausted
+        match client.list::<octocrab::models::Author>(route, None).await {
+            Ok(members) => Ok(members.into_iter().map(|member| member.login).collect()),
+            // team probably does not exist or is not visible, or some other error
+            Err(error) => {
+                log::error!(
+                    "the members of team {team} in organization {} could not be retrieved",
+                    client.config.owner
+                );
+                log::error!("{error}");
+                Err(IssueError::new("unknown team", error))
+            }
+        }
+    }
+
+    // determine whether the user has write, maintain, or admin permission on the repository
+    async fn can_write(&self, client: &Client<'_>, user: &str) -> Result<bool, IssueError> {
+        log::debug!("reading collaborator permission");
+        let route = format!(
+            "{}/collaborators/{}/permission",
+            client.repo_route(),
+            encode(user)
+        );
+        match client
+            .fetch::<CollaboratorPermission, ()>(http::Method::GET, &route, None)
+            .await
+        {
+            Ok(permission) => {
+                log::debug!(
+                    "{user} has the {} role on the repository",
+                    permission.role_name
+                );
+                Ok(is_writer(&permission.permission, &permission.role_name))
             }
+            Err(error) => match IssueError::new("unknown permission", error) {
+                // the user probably does not exist, and so certainly has no permission
+                IssueError::NotFound { .. } => {
+                    log::warn!(
+                        "the permission of {user} could not be found, and so it is assumed to be none"
+                    );
+                    Ok(false)
+                }
+                error => {
+                    log::error!(
+                        "the permission of {user} on the repository could not be retrieved"
+                    );
+                    log::error!("{error}");
+                    Err(error)
+                }
+            },
+        }
+    }
+
+    // convert the since str timestamp to utc datetime
+    fn since(&self) -> Result<Option<chrono::DateTime<chrono::Utc>>, IssueError> {
+        match self.since {
+            Some(since) => match chrono::DateTime::parse_from_rfc3339(since) {
+                Ok(since) => Ok(Some(since.to_utc())),
+                Err(error) => {
+                    log::error!("the since timestamp {since} is not in rfc3339 format");
+                    log::error!("{error}");
+                    Err(IssueError::Config("invalid since timestamp"))
+                }
+            },
+            None => Ok(None),
         }
     }
 
     // update a github issue according to configuration
-    async fn update<'octo>(
+    async fn update(
         &self,
-        issues: octocrab::issues::IssueHandler<'octo>,
-    ) -> Result<octocrab::models::issues::Issue, &str> {
+        client: &Client<'_>,
+    ) -> Result<octocrab::models::issues::Issue, IssueError> {
         // validate an issue number was specified
         match self.number {
             // issue number specified
             Some(number) => {
+                let route = format!("{}/issues/{number}", client.repo_route());
                 // unlock first if specified in case a comment is also specified
                 if self.lock == Some(false) {
-                    match issues.unlock(number).await {
+                    match client
+                        .write::<serde::de::IgnoredAny, ()>(
+                            "issue not unlocked",
+                            http::Method::DELETE,
+                            &format!("{route}/lock"),
+                            None,
+                        )
+                        .await
+                    {
                         Ok(_) => log::info!("issue number {number} unlocked"),
                         Err(error) => {
                             log::error!("the issue number {number} could not be unlocked");
                             log::error!("{error}");
-                            return Err("issue not unlocked");
+                            return Err(error);
                         }
                     }
                 }
                 // then create a comment if specified before potentially locking
                 if let Some(comment) = self.comment {
-                    match issues.create_comment(number, comment).await {
+                    match client
+                        .write::<serde::de::IgnoredAny, _>(
+                            "comment not added",
+                            http::Method::POST,
+                            &format!("{route}/comments"),
+                            Some(&serde_json::json!({ "body": comment })),
+                        )
+                        .await
+                    {
                         Ok(_) => log::info!("comment added to issue number {number}"),
                         Err(error) => {
                             log::error!("a comment could not be added to issue number {number}");
                             log::error!("{error}");
-                            return Err("comment not added");
+                            return Err(error);
                         }
                     }
                 }
                 // then lock if specified after potentially adding a comment
                 if self.lock == Some(true) {
-                    match issues.lock(number, LockReason::Resolved).await {
-                        Ok(_) => log::info!("issue number {number} locked as Resolved"),
+                    // the default lock reason is resolved
+                    let lock_reason = match self.lock_reason {
+                        Some(lock_reason) => str_to_lock_reason(lock_reason)?,
+                        None => LockReason::Resolved,
+                    };
+                    match client
+                        .write::<serde::de::IgnoredAny, _>(
+                            "issue not locked",
+                            http::Method::PUT,
+                            &format!("{route}/lock"),
+                            Some(&serde_json::json!({ "lock_reason": lock_reason })),
+                        )
+                        .await
+                    {
+                        Ok(_) => log::info!("issue number {number} locked as {lock_reason:?}"),
                         Err(error) => {
                             log::error!("the issue number {number} could not be locked");
                             log::error!("{error}");
-                            return Err("issue not locked");
+                            return Err(error);
+                        }
+                    }
+                }
+                // then add and remove individual labels which preserves the other labels
+                if let Some(add_labels) = &self.add_labels {
+                    match client
+                        .write::<serde::de::IgnoredAny, _>(
+                            "labels not added",
+                            http::Method::POST,
+                            &format!("{route}/labels"),
+                            Some(&serde_json::json!({ "labels": add_labels })),
+                        )
+                        .await
+                    {
+                        Ok(_) => log::info!("labels added to issue number {number}"),
+                        Err(error) => {
+                            log::error!("labels could not be added to issue number {number}");
+                            log::error!("{error}");
+                            return Err(error);
+                        }
+                    }
+                }
+                if let Some(remove_labels) = &self.remove_labels {
+                    for label in remove_labels {
+                        match client
+                            .write::<serde::de::IgnoredAny, ()>(
+                                "label not removed",
+                                http::Method::DELETE,
+                                &format!("{route}/labels/{}", encode(label)),
+                                None,
+                            )
+                            .await
+                        {
+                            Ok(_) => log::info!("label {label} removed from issue number {number}"),
+                            Err(error) => match error {
+                                // the label is already absent from the issue
+                                IssueError::NotFound { .. } => log::warn!(
+                                    "the label {label} could not be removed because it is not on issue number {number}"
+                                ),
+                                error => {
+                                    log::error!(
+                                        "the label {label} could not be removed from issue number {number}"
+                                    );
+                                    log::error!("{error}");
+                                    return Err(error);
+                                }
+                            },
+                        }
+                    }
+                }
+                // then add and remove individual assignees which preserves the other assignees
+                if let Some(add_assignees) = &self.add_assignees {
+                    match client
+                        .write::<serde::de::IgnoredAny, _>(
+                            "assignees not added",
+                            http::Method::POST,
+                            &format!("{route}/assignees"),
+                            Some(&serde_json::json!({ "assignees": add_assignees })),
+                        )
+                        .await
+                    {
+                        Ok(_) => log::info!("assignees added to issue number {number}"),
+                        Err(error) => {
+                            log::error!("assignees could not be added to issue number {number}");
+                            log::error!("{error}");
+                            return Err(error);
+                        }
+                    }
+                }
+                if let Some(remove_assignees) = &self.remove_assignees {
+                    match client
+                        .write::<serde::de::IgnoredAny, _>(
+                            "assignees not removed",
+                            http::Method::DELETE,
+                            &format!("{route}/assignees"),
+                            Some(&serde_json::json!({ "assignees": remove_assignees })),
+                        )
+                        .await
+                    {
+                        Ok(_) => log::info!("assignees removed from issue number {number}"),
+                        Err(error) => {
+                            log::error!(
+                                "assignees could not be removed from issue number {number}"
+                            );
+                            log::error!("{error}");
+                            return Err(error);
                         }
                     }
                 }
 
                 // build the issue
-                let mut issue = issues.update(number);
+                let mut issue = serde_json::json!({});
                 // ... with optional parameters
                 if let Some(title) = self.title {
-                    issue = issue.title(title);
+                    issue["title"] = title.into();
                 }
                 if let Some(body) = self.body {
-                    issue = issue.body(body);
+                    issue["body"] = body.into();
                 }
                 if let Some(labels) = &self.labels {
-                    issue = issue.labels(labels);
+                    issue["labels"] = labels.clone().into();
                 }
                 if let Some(assignees) = &self.assignees {
-                    issue = issue.assignees(assignees);
+                    issue["assignees"] = assignees.clone().into();
                 }
                 if let Some(state) = self.state {
                     // convert str state to issue_state
                     let issue_state = str_to_issue_state(state)?;
-                    issue = issue.state(issue_state);
+                    issue["state"] = serde_json::json!(issue_state);
+                }
+                if let Some(state_reason) = self.state_reason {
+                    // convert str state reason to issue state reason
+                    let issue_state_reason = str_to_state_reason(state_reason)?;
+                    issue["state_reason"] = serde_json::json!(issue_state_reason);
                 }
                 if let Some(milestone) = self.milestone {
-                    issue = issue.milestone(milestone);
+                    issue["milestone"] = milestone.into();
                 }
 
                 log::debug!("updating issue");
                 // send and await the issue
-                match issue.send().await {
+                match client
+                    .retry_write(async || {
+                        client
+                            .fetch(http::Method::PATCH, &route, Some(&issue))
+                            .await
+                            .map_err(|error| IssueError::new("issue not updated", error))
+                    })
+                    .await
+                {
                     // return updated issue
                     Ok(issue) => Ok(issue),
                     // issue number probably does not exist, or some other error
                     Err(error) => {
                         log::error!("the issue number {number} could not be updated");
                         log::error!("{error}");
-                        return Err("issue not updated");
+                        Err(error)
                     }
                 }
             }
@@ -382,11 +1623,66 @@ impl<'issue> Issue<'issue> {
                 log::error!(
                     "an issue number was not specified, and so an issue could not be updated"
                 );
-                return Err("issue number unspecified");
+                Err(IssueError::Config("issue number unspecified"))
             }
         }
     }
+
+    // verify that each assignee can be assigned to issues in the repository if validation is requested
+    async fn validate_assignees(
+        &self,
+        client: &Client<'_>,
+        assignees: &[String],
+    ) -> Result<(), IssueError> {
+        if !self.validate_assignees {
+            return Ok(());
+        }
+        for assignee in assignees {
+            // no content if the assignee can be assigned, and otherwise not found
+            let route = format!("{}/assignees/{}", client.repo_route(), encode(assignee));
+            let assignable = client
+                .retry(async || {
+                    match client
+                        .request::<serde::de::IgnoredAny, ()>(
+                            http::Method::GET,
+                            &route,
+                            http::HeaderMap::new(),
+                            None,
+                        )
+                        .await
+                    {
+                        Ok(_) => Ok(true),
+                        Err(error) => match IssueError::new("assignee not verified", error) {
+                            IssueError::NotFound { .. } => Ok(false),
+                            error => Err(error),
+                        },
+                    }
+                })
+                .await;
+            match assignable {
+                Ok(true) => log::debug!("{assignee} can be assigned to issues"),
+                Ok(false) => {
+                    log::error!(
+                        "{assignee} cannot be assigned to issues in the repository {}/{}",
+                        client.config.owner,
+                        client.config.repo
+                    );
+                    log::error!(
+                        "verify the login is spelled correctly and has access to the repository"
+                    );
+                    return Err(IssueError::Config("assignee cannot be assigned"));
+                }
+                Err(error) => {
+                    log::error!("{assignee} could not be verified as assignable");
+                    log::error!("{error}");
+                    return Err(error);
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
-mod tests;
+pub(super) mod tests;
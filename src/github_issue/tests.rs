@@ -1,5 +1,158 @@
 use super::*;
 
+// mock github server with the api url, and the method, path, and body of each request it received
+pub(crate) struct MockGithub {
+    pub(crate) api_url: String,
+    requests: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+impl MockGithub {
+    // the received requests in order e.g. "POST /repos/octocat/hello-world/issues/5/labels {"labels":["bug"]}"
+    pub(crate) fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+// serve the status and body of the first route which prefixes the path (or the method and path) of each request
+pub(crate) async fn mock_github(routes: Vec<(&str, u16, &str)>) -> MockGithub {
+    mock_github_headers(
+        routes
+            .into_iter()
+            .map(|(route, status, body)| (route, status, Vec::new(), body))
+            .collect(),
+    )
+    .await
+}
+
+// route prefix, status, headers, and body of a mock github response
+type MockRoute<'route> = (
+    &'route str,
+    u16,
+    Vec<(&'route str, &'route str)>,
+    &'route str,
+);
+
+// serve the status, headers, and body of the first route as above
+// unmodified (304) and no content (204) responses are served without a body
+pub(crate) async fn mock_github_headers(routes: Vec<MockRoute<'_>>) -> MockGithub {
+    let routes = routes
+        .into_iter()
+        .map(|(route, status, headers, body)| {
+            let headers = headers
+                .into_iter()
+                .map(|(name, value)| format!("{name}: {value}\r\n"))
+                .collect::<String>();
+            (route.to_owned(), status, headers, body.to_owned())
+        })
+        .collect::<Vec<(String, u16, String, String)>>();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("mock github server could not bind to a local port");
+    let github = MockGithub {
+        api_url: format!("http://{}", listener.local_addr().unwrap()),
+        requests: std::sync::Arc::default(),
+    };
+    let requests = std::sync::Arc::clone(&github.requests);
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            // read until the headers and the body of their content length are received
+            let mut received = Vec::new();
+            let mut buffer = vec![0; 8192];
+            let (head, body) = loop {
+                let length = tokio::io::AsyncReadExt::read(&mut stream, &mut buffer)
+                    .await
+                    .unwrap_or_default();
+                received.extend_from_slice(&buffer[..length]);
+                let request = String::from_utf8_lossy(&received).into_owned();
+                if let Some((head, body)) = request.split_once("\r\n\r\n") {
+                    let content_length = head
+                        .lines()
+                        .filter_map(|line| line.split_once(':'))
+                        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+                        .unwrap_or_default();
+                    if length == 0 || body.len() >= content_length {
+                        break (head.to_owned(), body.to_owned());
+                    }
+                } else if length == 0 {
+                    break (request, String::new());
+                }
+            };
+            let mut request_line = head.split_whitespace();
+            let method = request_line.next().unwrap_or_default();
+            let path = request_line.next().unwrap_or_default();
+            requests
+                .lock()
+                .unwrap()
+                .push(format!("{method} {path} {body}").trim_end().to_owned());
+            let (status, headers, body) = routes
+                .iter()
+                .find(|(route, ..)| {
+                    path.starts_with(route.as_str())
+                        || format!("{method} {path}").starts_with(route.as_str())
+                })
+                .map(|(_, status, headers, body)| (*status, headers.as_str(), body.as_str()))
+                .unwrap_or((404, "", r#"{"message":"Not Found"}"#));
+            let response = match status {
+                204 | 304 => {
+                    format!("HTTP/1.1 {status} Mock\r\n{headers}connection: close\r\n\r\n")
+                }
+                _ => format!(
+                    "HTTP/1.1 {status} Mock\r\n{headers}content-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                ),
+            };
+            let _ = tokio::io::AsyncWriteExt::write_all(&mut stream, response.as_bytes()).await;
+        }
+    });
+
+    github
+}
+
+// octocrab issue json in octocat/hello-world with the number, title, and body
+pub(crate) fn mock_issue(number: u64, title: &str, body: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": number,
+        "node_id": "MDU6SXNzdWUx",
+        "url": format!("https://api.github.com/repos/octocat/hello-world/issues/{number}"),
+        "repository_url": "https://api.github.com/repos/octocat/hello-world",
+        "labels_url": format!("https://api.github.com/repos/octocat/hello-world/issues/{number}/labels{{/name}}"),
+        "comments_url": format!("https://api.github.com/repos/octocat/hello-world/issues/{number}/comments"),
+        "events_url": format!("https://api.github.com/repos/octocat/hello-world/issues/{number}/events"),
+        "html_url": format!("https://github.com/octocat/hello-world/issues/{number}"),
+        "number": number,
+        "state": "open",
+        "title": title,
+        "body": body,
+        "user": {
+            "login": "octocat",
+            "id": 1,
+            "node_id": "MDQ6VXNlcjE=",
+            "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+            "gravatar_id": "",
+            "url": "https://api.github.com/users/octocat",
+            "html_url": "https://github.com/octocat",
+            "followers_url": "https://api.github.com/users/octocat/followers",
+            "following_url": "https://api.github.com/users/octocat/following{/other_user}",
+            "gists_url": "https://api.github.com/users/octocat/gists{/gist_id}",
+            "starred_url": "https://api.github.com/users/octocat/starred{/owner}{/repo}",
+            "subscriptions_url": "https://api.github.com/users/octocat/subscriptions",
+            "organizations_url": "https://api.github.com/users/octocat/orgs",
+            "repos_url": "https://api.github.com/users/octocat/repos",
+            "events_url": "https://api.github.com/users/octocat/events{/privacy}",
+            "received_events_url": "https://api.github.com/users/octocat/received_events",
+            "type": "User",
+            "site_admin": false
+        },
+        "labels": [],
+        "assignees": [],
+        "locked": false,
+        "comments": 0,
+        "created_at": "2024-01-01T00:00:00Z",
+        "updated_at": "2024-01-02T00:00:00Z"
+    })
+}
+
 #[test]
 fn test_action_to_string() {
     // validates ToString trait impl for action enum
@@ -7,6 +160,517 @@ fn test_action_to_string() {
     assert_eq!(String::from(Action::Read), String::from("Read"));
 }
 
+#[test]
+fn test_issue_error() {
+    // validates classification, display, and source for issue error
+    let error = IssueError::new(
+        "issue not created",
+        octocrab::Error::Installation {
+            backtrace: std::backtrace::Backtrace::capture(),
+        },
+    );
+    assert!(
+        matches!(
+            error,
+            IssueError::Other {
+                message: "issue not created",
+                ..
+            }
+        ),
+        "octocrab error without github response not classified as other",
+    );
+    assert_eq!(
+        error.to_string(),
+        "issue not created: octocrab failure",
+        "issue error did not display with message and classification",
+    );
+    assert!(
+        std::error::Error::source(&error).is_some(),
+        "issue error did not retain octocrab error as source",
+    );
+    // validates configuration error conversion and display
+    let error = IssueError::from("title unspecified");
+    assert_eq!(
+        error,
+        IssueError::Config("title unspecified"),
+        "str not converted to configuration issue error",
+    );
+    assert_eq!(
+        error.to_string(),
+        "title unspecified",
+        "configuration issue error did not display with message",
+    );
+    assert!(
+        std::error::Error::source(&error).is_none(),
+        "configuration issue error contained a source",
+    );
+}
+
+#[tokio::test]
+async fn test_issue_error_status() {
+    // validates classification of github response statuses
+    let github = mock_github(vec![
+        (
+            "/repos/mitodl/ol-infrastructure/issues/400",
+            400,
+            r#"{"message":"Bad Request"}"#,
+        ),
+        (
+            "/repos/mitodl/ol-infrastructure/issues/409",
+            409,
+            r#"{"message":"Conflict"}"#,
+        ),
+        (
+            "/repos/mitodl/ol-infrastructure/issues/410",
+            410,
+            r#"{"message":"Gone"}"#,
+        ),
+        (
+            "/repos/mitodl/ol-infrastructure/issues/422",
+            422,
+            r#"{"message":"Validation Failed"}"#,
+        ),
+        (
+            "/repos/mitodl/ol-infrastructure/issues/503",
+            503,
+            r#"{"message":"Unavailable"}"#,
+        ),
+    ])
+    .await;
+    let client = Client::new(ClientConfig {
+        owner: "mitodl",
+        repo: "ol-infrastructure",
+        api_url: Some(&github.api_url),
+        max_retries: Some(0),
+        ..Default::default()
+    })
+    .await
+    .expect("client for the mock github server was not constructed");
+    for (number, expected) in [
+        (400, "rejected by github"),
+        (404, "not found"),
+        (409, "rejected by github"),
+        (410, "rejected by github"),
+        (422, "validation failed"),
+        (503, "unexpected github response"),
+    ] {
+        let error = Issue {
+            number: Some(number),
+            ..Default::default()
+        }
+        .main(&client, Action::Read)
+        .await
+        .expect_err("unsuccessful response status did not return an error");
+        assert_eq!(
+            error.to_string(),
+            format!("unknown issue: {expected}"),
+            "response status {number} was misclassified",
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_issue_main_validate_assignees() {
+    // validate assignees are verified before the first write so that the update is not partially applied
+    let github = mock_github(vec![
+        ("/repos/mitodl/ol-infrastructure/assignees/octocat", 204, ""),
+        (
+            "/repos/mitodl/ol-infrastructure/issues/1",
+            500,
+            r#"{"message":"no write was expected"}"#,
+        ),
+    ])
+    .await;
+    let client = Client::new(ClientConfig {
+        owner: "mitodl",
+        repo: "ol-infrastructure",
+        api_url: Some(&github.api_url),
+        max_retries: Some(0),
+        ..Default::default()
+    })
+    .await
+    .expect("client for the mock github server was not constructed");
+    let gh_issue = Issue {
+        number: Some(1),
+        comment: Some("assigning"),
+        assignees: Some(vec![String::from("octocat")]),
+        add_assignees: Some(vec![String::from("ghost")]),
+        validate_assignees: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        gh_issue.main(&client, Action::Update).await,
+        Err(IssueError::Config("assignee cannot be assigned")),
+        "unassignable login did not error before the first write",
+    );
+}
+
+#[tokio::test]
+async fn test_issue_main_update_labels() {
+    // validate labels are added in one request, and removed individually with encoded names
+    let issue = mock_issue(5, "Release 1.4.0", "").to_string();
+    let github = mock_github(vec![
+        ("POST /repos/octocat/hello-world/issues/5/labels", 200, "[]"),
+        (
+            "DELETE /repos/octocat/hello-world/issues/5/labels/help%20wanted",
+            200,
+            "[]",
+        ),
+        (
+            "DELETE /repos/octocat/hello-world/issues/5/labels/stale",
+            404,
+            r#"{"message":"Label does not exist"}"#,
+        ),
+        ("PATCH /repos/octocat/hello-world/issues/5", 200, &issue),
+    ])
+    .await;
+    let client = Client::new(ClientConfig {
+        owner: "octocat",
+        repo: "hello-world",
+        api_url: Some(&github.api_url),
+        max_retries: Some(0),
+        ..Default::default()
+    })
+    .await
+    .expect("client for the mock github server was not constructed");
+    let gh_issue = Issue {
+        number: Some(5),
+        add_labels: Some(vec![String::from("bug"), String::from("triage")]),
+        remove_labels: Some(vec![String::from("help wanted"), String::from("stale")]),
+        ..Default::default()
+    };
+    // the label which is already absent from the issue only warns
+    assert!(
+        gh_issue.main(&client, Action::Update).await.is_ok(),
+        "the update did not succeed although the removed label was absent from the issue",
+    );
+    assert_eq!(
+        github.requests()[1..],
+        [
+            r#"POST /repos/octocat/hello-world/issues/5/labels {"labels":["bug","triage"]}"#,
+            "DELETE /repos/octocat/hello-world/issues/5/labels/help%20wanted",
+            "DELETE /repos/octocat/hello-world/issues/5/labels/stale",
+            "PATCH /repos/octocat/hello-world/issues/5 {}",
+        ],
+        "the labels were not added and removed with the expected requests",
+    );
+    // other errors while removing a label fail the update before the issue is patched
+    let github = mock_github(vec![(
+        "DELETE /repos/octocat/hello-world/issues/5/labels/help%20wanted",
+        403,
+        r#"{"message":"Resource not accessible by integration"}"#,
+    )])
+    .await;
+    let client = Client::new(ClientConfig {
+        owner: "octocat",
+        repo: "hello-world",
+        api_url: Some(&github.api_url),
+        max_retries: Some(0),
+        ..Default::default()
+    })
+    .await
+    .expect("client for the mock github server was not constructed");
+    let gh_issue = Issue {
+        number: Some(5),
+        remove_labels: Some(vec![String::from("help wanted")]),
+        ..Default::default()
+    };
+    assert!(
+        matches!(
+            gh_issue.main(&client, Action::Update).await,
+            Err(IssueError::Forbidden {
+                message: "label not removed",
+                ..
+            })
+        ),
+        "the forbidden label removal did not fail the update",
+    );
+    assert!(
+        !github
+            .requests()
+            .iter()
+            .any(|request| request.starts_with("PATCH")),
+        "the issue was patched although the label removal failed",
+    );
+}
+
+#[tokio::test]
+async fn test_issue_main_update_assignees() {
+    // validate assignees are added and removed with the assignees of each request body
+    let issue = mock_issue(5, "Release 1.4.0", "").to_string();
+    let github = mock_github(vec![
+        (
+            "POST /repos/octocat/hello-world/issues/5/assignees",
+            201,
+            &issue,
+        ),
+        (
+            "DELETE /repos/octocat/hello-world/issues/5/assignees",
+            200,
+            &issue,
+        ),
+        ("PATCH /repos/octocat/hello-world/issues/5", 200, &issue),
+    ])
+    .await;
+    let client = Client::new(ClientConfig {
+        owner: "octocat",
+        repo: "hello-world",
+        api_url: Some(&github.api_url),
+        max_retries: Some(0),
+        ..Default::default()
+    })
+    .await
+    .expect("client for the mock github server was not constructed");
+    let gh_issue = Issue {
+        number: Some(5),
+        add_assignees: Some(vec![String::from("octocat"), String::from("hubot")]),
+        remove_assignees: Some(vec![String::from("mona")]),
+        ..Default::default()
+    };
+    assert!(
+        gh_issue.main(&client, Action::Update).await.is_ok(),
+        "the update with assignee changes did not succeed",
+    );
+    assert_eq!(
+        github.requests()[1..],
+        [
+            r#"POST /repos/octocat/hello-world/issues/5/assignees {"assignees":["octocat","hubot"]}"#,
+            r#"DELETE /repos/octocat/hello-world/issues/5/assignees {"assignees":["mona"]}"#,
+            "PATCH /repos/octocat/hello-world/issues/5 {}",
+        ],
+        "the assignees were not added and removed with the expected requests",
+    );
+    // an error while adding assignees fails the update before the other writes
+    let github = mock_github(vec![(
+        "POST /repos/octocat/hello-world/issues/5/assignees",
+        403,
+        r#"{"message":"Resource not accessible by integration"}"#,
+    )])
+    .await;
+    let client = Client::new(ClientConfig {
+        owner: "octocat",
+        repo: "hello-world",
+        api_url: Some(&github.api_url),
+        max_retries: Some(0),
+        ..Default::default()
+    })
+    .await
+    .expect("client for the mock github server was not constructed");
+    assert!(
+        matches!(
+            gh_issue.main(&client, Action::Update).await,
+            Err(IssueError::Forbidden {
+                message: "assignees not added",
+                ..
+            })
+        ),
+        "the forbidden assignee addition did not fail the update",
+    );
+    assert_eq!(
+        github.requests().len(),
+        2,
+        "the update continued although the assignee addition failed",
+    );
+}
+
+#[tokio::test]
+async fn test_issue_retry() {
+    // validates retry waits and attempts according to error classification
+    // client without authentication verification because only the retries are exercised
+    let retry_client = |max_retries, max_retry_wait| Client {
+        octocrab: octocrab::Octocrab::default(),
+        config: ClientConfig {
+            owner: "mitodl",
+            repo: "ol-infrastructure",
+            max_retries,
+            max_retry_wait,
+            ..Default::default()
+        },
+        rate_limit: std::sync::Mutex::default(),
+    };
+    let octocrab_error = || octocrab::Error::Installation {
+        backtrace: std::backtrace::Backtrace::capture(),
+    };
+    let client = retry_client(None, Some(2));
+    assert_eq!(
+        client.retry_wait(&IssueError::Config("title unspecified"), 0, true),
+        None,
+        "configuration error was retried",
+    );
+    assert_eq!(
+        client.retry_wait(
+            &IssueError::NotFound {
+                message: "unknown issue",
+                source: octocrab_error(),
+            },
+            0,
+            true,
+        ),
+        None,
+        "not found error was retried",
+    );
+    let server_error = IssueError::Server {
+        message: "unknown issue",
+        source: octocrab_error(),
+    };
+    let wait = client
+        .retry_wait(&server_error, 0, true)
+        .expect("server error was not retried");
+    assert!(
+        wait >= std::time::Duration::from_millis(500) && wait <= std::time::Duration::from_secs(1),
+        "first retry wait was not the jittered initial backoff",
+    );
+    let wait = client
+        .retry_wait(&server_error, 5, true)
+        .expect("server error was not retried");
+    assert!(
+        wait <= std::time::Duration::from_secs(2),
+        "retry wait exceeded the maximum retry wait",
+    );
+    // secondary rate limit wait of one minute exceeds the maximum retry wait
+    let rate_limited_error = IssueError::RateLimited {
+        message: "unknown issue",
+        source: octocrab_error(),
+    };
+    assert_eq!(
+        client.retry_wait(&rate_limited_error, 0, true),
+        None,
+        "rate limit was retried despite exceeding the maximum retry wait",
+    );
+    assert_eq!(
+        retry_client(None, Some(120)).retry_wait(&rate_limited_error, 0, true),
+        Some(std::time::Duration::from_secs(60)),
+        "secondary rate limit was not retried after one minute",
+    );
+    // primary rate limit wait until the reset recorded from the rate limited response
+    let client = retry_client(None, Some(120));
+    *client.rate_limit.lock().unwrap() = RateLimit {
+        remaining: Some(0),
+        limit: Some(5000),
+        reset: Some(chrono::Utc::now().timestamp() + 90),
+        retry_after: None,
+    };
+    let wait = client
+        .retry_wait(&rate_limited_error, 0, true)
+        .expect("primary rate limit was not retried");
+    assert!(
+        wait > std::time::Duration::from_secs(60) && wait <= std::time::Duration::from_secs(90),
+        "primary rate limit was not retried after the reset",
+    );
+    // secondary rate limit wait according to the retry-after recorded from the rate limited response
+    *client.rate_limit.lock().unwrap() = RateLimit {
+        retry_after: Some(100),
+        ..Default::default()
+    };
+    assert_eq!(
+        client.retry_wait(&rate_limited_error, 0, false),
+        Some(std::time::Duration::from_secs(100)),
+        "rate limited write was not retried after the retry-after",
+    );
+    // writes are not retried for errors after which the write may have been applied
+    assert_eq!(
+        client.retry_wait(&server_error, 0, false),
+        None,
+        "server error was retried for a write",
+    );
+    assert_eq!(
+        client.retry_wait(
+            &IssueError::Network {
+                message: "comment not added",
+                source: octocrab_error(),
+            },
+            0,
+            false,
+        ),
+        None,
+        "network error was retried for a write which may have been sent",
+    );
+    assert!(
+        client
+            .retry_wait(
+                &IssueError::Network {
+                    message: "comment not added",
+                    source: octocrab::Error::Other {
+                        source: Box::new(std::io::Error::from(
+                            std::io::ErrorKind::ConnectionRefused
+                        )),
+                        backtrace: std::backtrace::Backtrace::capture(),
+                    },
+                },
+                0,
+                false,
+            )
+            .is_some(),
+        "refused connection was not retried for a write",
+    );
+    // retryable errors are attempted until the maximum number of retries
+    let attempts = std::cell::Cell::new(0);
+    let result: Result<(), IssueError> = retry_client(Some(2), Some(0))
+        .retry(async || {
+            attempts.set(attempts.get() + 1);
+            Err(IssueError::Network {
+                message: "unknown issue",
+                source: octocrab_error(),
+            })
+        })
+        .await;
+    assert!(
+        matches!(result, Err(IssueError::Network { .. })),
+        "retries did not return the final error",
+    );
+    assert_eq!(
+        attempts.get(),
+        3,
+        "network error was not retried the maximum number of times",
+    );
+    // other errors are attempted once
+    attempts.set(0);
+    let result: Result<(), IssueError> = retry_client(Some(2), Some(0))
+        .retry(async || {
+            attempts.set(attempts.get() + 1);
+            Err(IssueError::Config("title unspecified"))
+        })
+        .await;
+    assert_eq!(
+        result,
+        Err(IssueError::Config("title unspecified")),
+        "retries did not return the error",
+    );
+    assert_eq!(attempts.get(), 1, "configuration error was retried");
+}
+
+#[test]
+fn test_rate_limit_from_headers() {
+    // validates rate limit parsed from response headers
+    let mut headers = http::HeaderMap::new();
+    headers.insert(
+        "x-ratelimit-remaining",
+        http::HeaderValue::from_static("42"),
+    );
+    headers.insert("x-ratelimit-limit", http::HeaderValue::from_static("5000"));
+    headers.insert(
+        "x-ratelimit-reset",
+        http::HeaderValue::from_static("1700000000"),
+    );
+    headers.insert("retry-after", http::HeaderValue::from_static("30"));
+    assert_eq!(
+        RateLimit::from_headers(&headers),
+        RateLimit {
+            remaining: Some(42),
+            limit: Some(5000),
+            reset: Some(1700000000),
+            retry_after: Some(30),
+        },
+        "rate limit not parsed from response headers",
+    );
+    // validates rate limit unknown without headers e.g. github enterprise server without rate limiting
+    assert_eq!(
+        RateLimit::from_headers(&http::HeaderMap::new()),
+        RateLimit::default(),
+        "rate limit parsed from response without headers",
+    );
+}
+
 #[test]
 fn test_str_to_issue_state() {
     // validates issue open and closed conversions
@@ -22,6 +686,66 @@ fn test_str_to_issue_state() {
         "failed to convert closed str to closed enum"
     );
 }
+#[test]
+fn test_str_to_state_reason() {
+    // validates issue state reason conversions
+    assert_eq!(
+        str_to_state_reason("completed"),
+        Ok(IssueStateReason::Completed),
+        "failed to convert completed str to completed enum"
+    );
+    assert_eq!(
+        str_to_state_reason("not_planned"),
+        Ok(IssueStateReason::NotPlanned),
+        "failed to convert not_planned str to not planned enum"
+    );
+    assert_eq!(
+        str_to_state_reason("wontfix"),
+        Err("the issue state reason must be either completed, not_planned, reopened, or duplicate"),
+        "failed to error on invalid state reason"
+    );
+}
+
+#[test]
+fn test_str_to_lock_reason() {
+    // validates lock reason conversions
+    assert!(
+        matches!(str_to_lock_reason("too heated"), Ok(LockReason::TooHeated)),
+        "failed to convert too heated str to too heated enum"
+    );
+    assert!(
+        matches!(str_to_lock_reason("off-topic"), Ok(LockReason::OffTopic)),
+        "failed to convert off-topic str to off topic enum"
+    );
+    assert!(
+        matches!(
+            str_to_lock_reason("heated"),
+            Err("the lock reason must be either off-topic, too heated, resolved, or spam")
+        ),
+        "failed to error on invalid lock reason"
+    );
+}
+
+#[test]
+fn test_is_writer() {
+    assert!(
+        is_writer("admin", "admin"),
+        "admin permission was not a writer"
+    );
+    assert!(
+        is_writer("write", "maintain"),
+        "maintain role was not a writer"
+    );
+    assert!(is_writer("write", "write"), "write role was not a writer");
+    assert!(
+        is_writer("", "maintain"),
+        "maintain role without legacy permission was not a writer"
+    );
+    assert!(!is_writer("read", "triage"), "triage role was a writer");
+    assert!(!is_writer("read", "read"), "read role was a writer");
+    assert!(!is_writer("none", ""), "no permission was a writer");
+}
+
 #[test]
 fn test_str_to_params_state() {
     // octocrab::params::State does not implement Eq
@@ -37,171 +761,77 @@ fn test_str_to_params_state() {
 }
 
 #[test]
-fn test_issue_new() {
-    // validates basic read constructor
+fn test_issue_default() {
+    // validates unspecified parameters default to none for read
     assert_eq!(
-        Issue::new(
-            None,
-            "my_org",
-            "my_repo",
-            None,
-            None,
-            None,
-            None,
-            None,
-            Some(100),
-            None,
-            None,
-            None,
-            None
-        ),
         Issue {
-            pat: None,
-            owner: "my_org",
-            repo: "my_repo",
+            number: Some(100),
+            ..Default::default()
+        },
+        Issue {
             title: None,
             body: None,
             labels: None,
             assignees: None,
             creator: None,
+            since: None,
+            per_page: None,
+            max_issues: None,
             number: Some(100),
+            etag: None,
             comment: None,
             lock: None,
+            lock_reason: None,
+            add_labels: None,
+            remove_labels: None,
+            add_assignees: None,
+            remove_assignees: None,
+            validate_assignees: false,
             state: None,
-            //params_state: None,
-            milestone: None
+            state_reason: None,
+            milestone: None,
         },
         "failed to construct Issue for read"
     );
-
-    // validate basic create constructor
+    // validates unspecified client configuration defaults to an unauthenticated github api client
     assert_eq!(
-        Issue::new(
-            None,
-            "my_org",
-            "my_repo",
-            Some("my issue"),
-            Some("my body"),
-            Some(vec![String::from("label")]),
-            Some(vec![String::from("assignee")]),
-            None,
-            None,
-            None,
-            None,
-            None,
-            None
-        ),
-        Issue {
-            pat: None,
+        ClientConfig {
             owner: "my_org",
             repo: "my_repo",
-            title: Some("my issue"),
-            body: Some("my body"),
-            labels: Some(vec![String::from("label")]),
-            assignees: Some(vec![String::from("assignee")]),
-            creator: None,
-            number: None,
-            comment: None,
-            lock: None,
-            state: None,
-            //params_state: None,
-            milestone: None
+            ..Default::default()
         },
-        "failed to construct Issue for create"
-    );
-
-    // validate basic update constructor
-    assert_eq!(
-        Issue::new(
-            None,
-            "my_org",
-            "my_repo",
-            None,
-            Some("updated body"),
-            Some(vec![String::from("label1"), String::from("label2")]),
-            Some(vec![String::from("assignee1")]),
-            None,
-            Some(42),
-            Some("good comment"),
-            Some(true),
-            Some("closed"),
-            Some(12)
-        ),
-        Issue {
+        ClientConfig {
             pat: None,
             owner: "my_org",
             repo: "my_repo",
-            title: None,
-            body: Some("updated body"),
-            labels: Some(vec![String::from("label1"), String::from("label2")]),
-            assignees: Some(vec![String::from("assignee1")]),
-            creator: None,
-            number: Some(42),
-            comment: Some("good comment"),
-            lock: Some(true),
-            state: Some("closed"),
-            //params_state: None,
-            milestone: Some(12),
+            api_url: None,
+            app_id: None,
+            private_key: None,
+            installation_id: None,
+            max_retries: None,
+            max_retry_wait: None,
+            min_rate_limit_remaining: None,
         },
-        "failed to construct Issue for update"
-    );
-
-    // validate basic list constructor
-    assert_eq!(
-        Issue::new(
-            None,
-            "my_org",
-            "my_repo",
-            None,
-            None,
-            None,
-            Some(vec![String::from("assignee1")]),
-            Some("creator1"),
-            None,
-            None,
-            None,
-            Some("open"),
-            Some(5)
-        ),
-        Issue {
-            pat: None,
-            owner: "my_org",
-            repo: "my_repo",
-            title: None,
-            body: None,
-            labels: None,
-            assignees: Some(vec![String::from("assignee1")]),
-            creator: Some("creator1"),
-            number: None,
-            comment: None,
-            lock: None,
-            state: Some("open"),
-            //params_state: None,
-            milestone: Some(5),
-        },
-        "failed to construct Issue for list"
+        "failed to construct ClientConfig"
     );
 }
 
 #[tokio::test]
 async fn test_issue_main_read() {
     // validate issue returned when read from main
-    let gh_issue = Issue::new(
-        None,
-        "mitodl",
-        "ol-infrastructure",
-        None,
-        None,
-        None,
-        None,
-        None,
-        Some(100),
-        None,
-        None,
-        None,
-        None,
-    );
-    let issue = gh_issue.main(Action::Read).await;
+    let config = ClientConfig {
+        owner: "mitodl",
+        repo: "ol-infrastructure",
+        ..Default::default()
+    };
+    let client = Client::new(config)
+        .await
+        .expect("client with valid configuration was not constructed");
+    let gh_issue = Issue {
+        number: Some(100),
+        ..Default::default()
+    };
+    let issue = gh_issue.main(&client, Action::Read).await;
     assert_eq!(
         issue.unwrap().state,
         octocrab::models::IssueState::Closed,
@@ -212,22 +842,21 @@ async fn test_issue_main_read() {
 #[tokio::test]
 async fn test_issue_main_list() {
     // validate one issue of multiple listed returned from main
-    let gh_issue = Issue::new(
-        None,
-        "mitodl",
-        "ol-infrastructure",
-        None,
-        None,
-        None,
-        Some(vec![String::from("pdpinch")]),
-        None,
-        None,
-        None,
-        None,
-        Some("closed"),
-        Some(3),
-    );
-    let issue = gh_issue.main(Action::List).await;
+    let config = ClientConfig {
+        owner: "mitodl",
+        repo: "ol-infrastructure",
+        ..Default::default()
+    };
+    let client = Client::new(config)
+        .await
+        .expect("client with valid configuration was not constructed");
+    let gh_issue = Issue {
+        assignees: Some(vec![String::from("pdpinch")]),
+        state: Some("closed"),
+        milestone: Some(3),
+        ..Default::default()
+    };
+    let issue = gh_issue.main(&client, Action::List).await;
     assert_eq!(
         issue.unwrap().number,
         833,
@@ -236,71 +865,260 @@ async fn test_issue_main_list() {
 }
 
 #[tokio::test]
-async fn test_errors() {
-    // validate errors
-    let gh_issue = Issue::new(
-        None,
-        "mitodl",
-        "ol-infrastructure",
-        None,
-        None,
-        None,
-        Some(vec![String::from("foo"), String::from("bar")]),
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
+async fn test_issue_main_list_pages() {
+    // validate issues listed through multiple pages up to maximum returned from main list
+    let config = ClientConfig {
+        owner: "mitodl",
+        repo: "ol-infrastructure",
+        ..Default::default()
+    };
+    let client = Client::new(config)
+        .await
+        .expect("client with valid configuration was not constructed");
+    let gh_issue = Issue {
+        per_page: Some(10),
+        max_issues: Some(25),
+        state: Some("closed"),
+        ..Default::default()
+    };
+    let issues = gh_issue.main_list(&client).await;
+    assert_eq!(
+        issues.unwrap().len(),
+        25,
+        "maximum number of issues from multiple pages from mitodl/ol-infrastructure not returned correctly",
     );
-    let gh_issue_four = Issue::new(
-        None,
-        "mitodl",
-        "ol-infrastructure",
-        None,
-        None,
-        None,
-        Some(vec![String::from("blarghmatey")]),
-        None,
-        None,
-        None,
-        None,
-        None,
-        None,
+}
+
+#[tokio::test]
+async fn test_issue_main_list_invalid_since() {
+    // validate since timestamp must be rfc3339 for list
+    let config = ClientConfig {
+        owner: "mitodl",
+        repo: "ol-infrastructure",
+        ..Default::default()
+    };
+    let client = Client::new(config)
+        .await
+        .expect("client with valid configuration was not constructed");
+    let gh_issue = Issue {
+        since: Some("yesterday"),
+        ..Default::default()
+    };
+    assert_eq!(
+        gh_issue.main_list(&client).await,
+        Err(IssueError::Config("invalid since timestamp")),
+        "attempted list with invalid since timestamp did not error expectedly",
     );
+}
+
+#[tokio::test]
+async fn test_issue_main_invalid_api_url() {
+    // validate api url must be a valid url for client
+    let config = ClientConfig {
+        owner: "mitodl",
+        repo: "ol-infrastructure",
+        api_url: Some("not a url"),
+        ..Default::default()
+    };
+    assert_eq!(
+        Client::new(config).await.err(),
+        Some(IssueError::Config("invalid api url")),
+        "client construction with invalid api url did not error expectedly",
+    );
+}
+
+#[tokio::test]
+async fn test_issue_main_invalid_lock_reason() {
+    // validate lock reason before any interaction
+    let config = ClientConfig {
+        owner: "mitodl",
+        repo: "ol-infrastructure",
+        ..Default::default()
+    };
+    let client = Client::new(config)
+        .await
+        .expect("client with valid configuration was not constructed");
+    let gh_issue = Issue {
+        number: Some(100),
+        comment: Some("this comment must not be created"),
+        lock: Some(true),
+        lock_reason: Some("heated"),
+        ..Default::default()
+    };
+    assert_eq!(
+        gh_issue.main(&client, Action::Update).await,
+        Err(IssueError::Config(
+            "the lock reason must be either off-topic, too heated, resolved, or spam"
+        )),
+        "attempted update with invalid lock reason did not error expectedly",
+    );
+}
+
+#[tokio::test]
+async fn test_issue_main_invalid_pat() {
+    // validate invalid pat errors instead of continuing with unauthenticated client
+    let config = ClientConfig {
+        pat: Some("abcdefg12345"),
+        owner: "mitodl",
+        repo: "ol-infrastructure",
+        ..Default::default()
+    };
+    assert_eq!(
+        Client::new(config).await.err(),
+        Some(IssueError::Unauthorized {
+            message: "authentication failed",
+            source: octocrab::Error::Installation {
+                backtrace: std::backtrace::Backtrace::capture(),
+            },
+        }),
+        "client construction with invalid pat did not error expectedly",
+    );
+}
+
+#[tokio::test]
+async fn test_issue_main_rate_limit_not_enabled() {
+    // validate github enterprise server without rate limiting verifies the pat with the authenticated user instead
+    let rate_limit_not_enabled = (
+        "/rate_limit",
+        404,
+        r#"{"message":"Rate limiting is not enabled."}"#,
+    );
+    let github = mock_github(vec![
+        rate_limit_not_enabled,
+        ("/user", 200, r#"{"login":"octocat"}"#),
+    ])
+    .await;
+    let config = ClientConfig {
+        pat: Some("abcdefg12345"),
+        owner: "mitodl",
+        repo: "ol-infrastructure",
+        api_url: Some(&github.api_url),
+        min_rate_limit_remaining: Some(100),
+        ..Default::default()
+    };
+    assert!(
+        Client::new(config).await.is_ok(),
+        "client construction without rate limiting did not verify the pat with the authenticated user",
+    );
+    let github = mock_github(vec![
+        rate_limit_not_enabled,
+        ("/user", 401, r#"{"message":"Bad credentials"}"#),
+    ])
+    .await;
+    assert_eq!(
+        Client::new(ClientConfig {
+            api_url: Some(&github.api_url),
+            ..config
+        })
+        .await
+        .err(),
+        Some(IssueError::Unauthorized {
+            message: "authentication failed",
+            source: octocrab::Error::Installation {
+                backtrace: std::backtrace::Backtrace::capture(),
+            },
+        }),
+        "client construction without rate limiting and with invalid pat did not error expectedly",
+    );
+}
+
+#[tokio::test]
+async fn test_issue_main_app_errors() {
+    // validate github app credentials for client construction
+    let app_config = |pat, app_id, private_key, installation_id| ClientConfig {
+        pat,
+        owner: "mitodl",
+        repo: "ol-infrastructure",
+        app_id,
+        private_key,
+        installation_id,
+        ..Default::default()
+    };
+    assert_eq!(
+        Client::new(app_config(None, Some(12345), None, Some(67890)))
+            .await
+            .err(),
+        Some(IssueError::Config("incomplete github app credentials")),
+        "client construction with incomplete github app credentials did not error expectedly",
+    );
+    assert_eq!(
+        Client::new(app_config(
+            Some("abcdefg12345"),
+            Some(12345),
+            Some("key"),
+            Some(67890)
+        ))
+        .await
+        .err(),
+        Some(IssueError::Config("multiple authentication methods")),
+        "client construction with pat and github app credentials did not error expectedly",
+    );
+    assert_eq!(
+        Client::new(app_config(
+            None,
+            Some(12345),
+            Some("not a key"),
+            Some(67890)
+        ))
+        .await
+        .err(),
+        Some(IssueError::Config("invalid private key")),
+        "client construction with invalid github app private key did not error expectedly",
+    );
+}
+
+#[tokio::test]
+async fn test_errors() {
+    // validate errors
+    let config = ClientConfig {
+        owner: "mitodl",
+        repo: "ol-infrastructure",
+        ..Default::default()
+    };
+    let client = Client::new(config)
+        .await
+        .expect("client with valid configuration was not constructed");
+    let gh_issue = Issue {
+        assignees: Some(vec![String::from("foo"), String::from("bar")]),
+        ..Default::default()
+    };
+    let gh_issue_four = Issue {
+        assignees: Some(vec![String::from("blarghmatey")]),
+        ..Default::default()
+    };
     // validate title required for create error
-    let issue = gh_issue.main(Action::Create).await;
+    let issue = gh_issue.main(&client, Action::Create).await;
     assert_eq!(
         issue,
-        Err("title unspecified"),
+        Err(IssueError::Config("title unspecified")),
         "attempted create without specified title did not error expectedly",
     );
     // validate issue number required for read
-    let issue_two = gh_issue.main(Action::Read).await;
+    let issue_two = gh_issue.main(&client, Action::Read).await;
     assert_eq!(
         issue_two,
-        Err("issue number unspecified"),
+        Err(IssueError::Config("issue number unspecified")),
         "attempted read without specified number did not error expectedly",
     );
     // validate only one assignee for list
-    let issue_three = gh_issue.main(Action::List).await;
+    let issue_three = gh_issue.main(&client, Action::List).await;
     assert_eq!(
         issue_three,
-        Err("multiple assignees and list action"),
+        Err(IssueError::Config("multiple assignees and list action")),
         "attempted list with multiple assignees did not error expectedly",
     );
     // validate only one issue returned for list
-    let issue_four = gh_issue_four.main(Action::List).await;
+    let issue_four = gh_issue_four.main(&client, Action::List).await;
     assert_eq!(
         issue_four,
-        Err("unexpected number of issues"),
+        Err(IssueError::Config("unexpected number of issues")),
         "attempted list with multiple issues returned did not error expectedly",
     );
     // validate issue number required for update
-    let issue_five = gh_issue.main(Action::Update).await;
+    let issue_five = gh_issue.main(&client, Action::Update).await;
     assert_eq!(
         issue_five,
-        Err("issue number unspecified"),
+        Err(IssueError::Config("issue number unspecified")),
         "attempted update without specified number did not error expectedly",
     );
 }
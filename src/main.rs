@@ -1,105 +1,56 @@
-use env_logger;
-use log;
-
 use concourse_resource::*;
-use octocrab::models::IssueState;
+
+use error::StepError;
 
 mod concourse;
+mod error;
 mod github_issue;
 
 struct GithubIssue {}
 
+// issue event types which can trigger during check
+const EVENT_TYPES: [&str; 8] = [
+    "labeled",
+    "unlabeled",
+    "assigned",
+    "unassigned",
+    "milestoned",
+    "demilestoned",
+    "closed",
+    "reopened",
+];
+
 impl concourse_resource::Resource for GithubIssue {
     // implementations for inputs and outputs
     type Source = concourse::Source;
     type Version = concourse::Version;
     type InParams = concourse_resource::Empty;
-    type InMetadata = concourse_resource::Empty;
+    type InMetadata = concourse::InMetadata;
     type OutParams = concourse::OutParams;
     type OutMetadata = concourse::OutMetadata;
 
     // implementations for steps
-    /// Performs the check step for the resource. Returns a single sized vector of version of state string if the input issue is open (no trigger), and a two sized vector of version of state string if the input issue is closed (trigger). For convenience and standardization the former return is "open", and the latter is "open" and "closed".
+    /// Performs the check step for the resource. Returns the input version and a new version of the issue number, state, and transition time if the issue state matches the trigger and it occurred after the input version. Otherwise returns only the input version (if any).
     #[tokio::main]
     async fn resource_check(
         source: Option<Self::Source>,
-        _version: Option<Self::Version>,
+        version: Option<Self::Version>,
     ) -> Vec<Self::Version> {
         // init logger
         let _ = env_logger::try_init();
 
-        // validate and unwrap source
-        let source = source.expect("source is required for the Github Issue resource");
-
-        if source.owner().is_empty() || source.repo().is_empty() {
-            panic!(
-                "both 'owner' and 'repo' are required parameters for the Github Issue resource 'source'"
-            );
-        }
-
-        // return immediately with two sized vector if check step skip requested (e.g. source for out/put+create)
-        if source.skip_check() {
-            log::info!(
-                "the check step will be skipped because 'skip_check' was set to true in source"
-            );
-            return vec![
-                concourse::Version::new(IssueState::Open),
-                concourse::Version::new(IssueState::Closed),
-            ];
-        }
-
-        // construct an issue...
-        let gh_issue = github_issue::Issue::new(
-            source.pat(),
-            source.owner(),
-            source.repo(),
-            None,
-            None,
-            source.labels(),
-            source.assignee(),
-            source.creator(),
-            source.number(),
-            None,
-            None,
-            source.state(),
-            source.milestone(),
-        );
-        // ...determine the action...
-        let action = match source.number() {
-            Some(_) => github_issue::Action::Read,
-            None => github_issue::Action::List,
-        };
-        // ...and return the octocrab github issue
-        let issue = match gh_issue.main(action).await {
-            Ok(issue) => issue,
-            Err(error) => {
-                log::error!("{error}");
-                panic!("the check step was unable to return a github issue from the source values");
-            }
-        };
-        log::info!(
-            "the github issue information was successfully retrieved for number {}",
-            issue.number
-        );
-
-        // return two sized version vector if issue state matches trigger, and one sized if otherwise
-        if issue.state == source.trigger() {
-            vec![
-                concourse::Version::new(IssueState::Open),
-                concourse::Version::new(IssueState::Closed),
-            ]
-        } else {
-            vec![concourse::Version::new(issue.state)]
-        }
+        Self::check(source, version)
+            .await
+            .unwrap_or_else(|error| Self::exit(error))
     }
 
-    /// Dummies the in step as it performs no functionality.
+    /// Performs the in step for the resource. Retrieves the Github issue specified by the source, and writes its information to files in the output directory for usage in subsequent steps.
     #[tokio::main]
     async fn resource_in(
-        _source: Option<Self::Source>,
-        _version: Self::Version,
+        source: Option<Self::Source>,
+        version: Self::Version,
         _params: Option<Self::InParams>,
-        _output_path: &str,
+        output_path: &str,
     ) -> Result<
         concourse_resource::InOutput<Self::Version, Self::InMetadata>,
         Box<dyn std::error::Error>,
@@ -107,19 +58,41 @@ impl concourse_resource::Resource for GithubIssue {
         // init logger
         let _ = env_logger::try_init();
 
+        // validate and unwrap source
+        let source = source.ok_or("source is required for the Github Issue resource")?;
+
+        // retrieve the octocrab github issue with the version number, or else the source values
+        let number = version.number().or(source.number());
+        let issue = async {
+            let client = Self::client(&source, None).await?;
+            Self::source_issue(&client, &source, number).await
+        }
+        .await
+        .map_err(|error| {
+            StepError::issue(
+                "the in step was unable to return a github issue from the source values",
+                error,
+            )
+        })?;
         log::info!(
-            "reminder: the in step is only to be used for an efficient check step with minimal overhead"
-        );
-        log::info!(
-            "there is no actual functionality for the in step, and the version and metadata are dummied"
+            "the github issue information was successfully retrieved for number {}",
+            issue.number
         );
+
+        // write issue information to files for subsequent steps
+        Self::write_issue_files(&issue, output_path)?;
+        Self::write_command_files(&version, output_path)?;
+        Self::write_reaction_files(&version, output_path)?;
+        log::info!("the github issue information was written to files in '{output_path}'");
+
+        // return in step output
         Ok(concourse_resource::InOutput {
-            version: concourse::Version::new(IssueState::Open),
-            metadata: None,
+            version,
+            metadata: Some(Self::metadata(issue)),
         })
     }
 
-    /// Performs the out step for the resource. Creates a new Github issue based on the parameters.
+    /// Performs the out step for the resource. Creates a new Github issue, or updates an existing Github issue, based on the parameters.
     #[tokio::main]
     async fn resource_out(
         source: Option<Self::Source>,
@@ -129,49 +102,274 @@ impl concourse_resource::Resource for GithubIssue {
         // init logger
         let _ = env_logger::try_init();
 
+        Self::out(source, params, input_path)
+            .await
+            .unwrap_or_else(|error| Self::exit(error))
+    }
+}
+
+// helper functions if we need them
+impl GithubIssue {
+    // perform the check step and return the versions, or the error for reporting
+    async fn check(
+        source: Option<concourse::Source>,
+        version: Option<concourse::Version>,
+    ) -> Result<Vec<concourse::Version>, StepError> {
+        // validate and unwrap source
+        let source = source.ok_or("source is required for the Github Issue resource")?;
+
+        if source.owner().is_empty() || source.repo().is_empty() {
+            return Err(
+                "both 'owner' and 'repo' are required parameters for the Github Issue resource 'source'"
+                    .into(),
+            );
+        }
+
+        // return immediately with input version if check step skip requested (e.g. source for out/put+create)
+        if source.skip_check() {
+            log::info!(
+                "the check step will be skipped because 'skip_check' was set to true in source"
+            );
+            return Ok(version.into_iter().collect());
+        }
+
+        // validate and compile the compound trigger conditions before any interaction
+        let conditions = match source.conditions() {
+            // the conditions are for the issue, and so they are not applicable to comment, event, or reaction triggers
+            Some(_)
+                if source.comments()
+                    || source.commands().is_some()
+                    || source.events().is_some()
+                    || source.reaction().is_some() =>
+            {
+                return Err(
+                    "the 'conditions' cannot be combined with 'comments', 'commands', 'events', or 'reaction' in 'source'"
+                        .into(),
+                );
+            }
+            Some(conditions) => Some(conditions.compile().map_err(|error| {
+                format!("the 'conditions' pattern is not a valid regular expression: {error}")
+            })?),
+            None => None,
+        };
+
+        // return versions for each new comment or slash command on the issue if comments or commands requested
+        if source.comments() || source.commands().is_some() {
+            let number = source
+                .number()
+                .ok_or("'number' is required in 'source' for comment triggers")?;
+            let pattern = match source.comment_pattern() {
+                Some(pattern) => Some(regex::Regex::new(pattern).map_err(|error| {
+                    format!("the 'comment_pattern' is not a valid regular expression: {error}")
+                })?),
+                None => None,
+            };
+            return match Self::check_comments(&source, number, version.clone(), pattern.as_ref())
+                .await
+            {
+                Ok(versions) => Ok(versions),
+                Err(github_issue::IssueError::QuotaReserved(_)) => {
+                    Ok(Self::reserved_quota_versions(version))
+                }
+                Err(error) => Err(StepError::issue(
+                    "the check step was unable to return github issue comments from the source values",
+                    error,
+                )),
+            };
+        }
+
+        // return versions for each new event on the issue if events requested
+        if let Some(events) = source.events() {
+            let number = source
+                .number()
+                .ok_or("'number' is required in 'source' for event triggers")?;
+            if let Some(event) = events
+                .iter()
+                .find(|event| !EVENT_TYPES.contains(&event.as_str()))
+            {
+                return Err(format!(
+                    "the event type '{event}' must be one of {}",
+                    EVENT_TYPES.join(", ")
+                )
+                .into());
+            }
+            return match Self::check_events(&source, number, version.clone()).await {
+                Ok(versions) => Ok(versions),
+                Err(github_issue::IssueError::QuotaReserved(_)) => {
+                    Ok(Self::reserved_quota_versions(version))
+                }
+                Err(error) => Err(StepError::issue(
+                    "the check step was unable to return github issue events from the source values",
+                    error,
+                )),
+            };
+        }
+
+        // return a version when the reaction count reaches the threshold if reaction requested
+        if source.reaction().is_some() {
+            let number = source
+                .number()
+                .ok_or("'number' is required in 'source' for reaction triggers")?;
+            if source.reaction_threshold() == 0 {
+                return Err("the 'reaction_threshold' must be at least 1".into());
+            }
+            return match Self::check_reactions(&source, number, version.clone()).await {
+                Ok(versions) => Ok(versions),
+                Err(github_issue::IssueError::QuotaReserved(_)) => {
+                    Ok(Self::reserved_quota_versions(version))
+                }
+                Err(error) => Err(StepError::issue(
+                    "the check step was unable to return github issue reactions from the source values",
+                    error,
+                )),
+            };
+        }
+
+        // return versions for each issue from the filtered list if multiple requested
+        if source.multiple() {
+            return match Self::check_multiple(&source, version.clone(), conditions.as_ref()).await {
+                Ok(versions) => Ok(versions),
+                Err(github_issue::IssueError::QuotaReserved(_)) => {
+                    Ok(Self::reserved_quota_versions(version))
+                }
+                Err(error) => Err(StepError::issue(
+                    "the check step was unable to return github issues from the source values",
+                    error,
+                )),
+            };
+        }
+
+        // return the input version and a new version if the issue matches the trigger
+        match Self::check_single(&source, version.clone(), conditions.as_ref()).await {
+            Ok(versions) => Ok(versions),
+            Err(github_issue::IssueError::QuotaReserved(_)) => {
+                Ok(Self::reserved_quota_versions(version))
+            }
+            Err(error) => Err(StepError::issue(
+                "the check step was unable to return a github issue from the source values",
+                error,
+            )),
+        }
+    }
+
+    // perform the out/put step and return the output, or the error for reporting
+    async fn out(
+        source: Option<concourse::Source>,
+        params: Option<concourse::OutParams>,
+        input_path: &str,
+    ) -> Result<concourse_resource::OutOutput<concourse::Version, concourse::OutMetadata>, StepError>
+    {
         // validate source and params
-        let source = source.expect("source is required for the Github Issue resource");
-        let params = params.expect("params is required for the Github Issue resource out/put step");
+        let source = source.ok_or("source is required for the Github Issue resource")?;
+        let params =
+            params.ok_or("params is required for the Github Issue resource out/put step")?;
 
-        // construct an issue...
-        let gh_issue = github_issue::Issue::new(
-            source.pat(),
-            source.owner(),
-            source.repo(),
-            params.title(),
-            params.body(),
-            params.labels(),
-            params.assignees(),
-            None,
-            source.number(),
-            params.comment(),
-            params.lock(),
-            params.state(),
-            params.milestone(),
-        );
-        // ...determine the action...
-        let action = match source.number() {
+        // resolve title, body, and comment from the inline params or else files relative to the input path
+        let (title, body, comment) = match (
+            Self::param_or_file("title", params.title(), params.title_file(), input_path),
+            Self::param_or_file("body", params.body(), params.body_file(), input_path),
+            Self::param_or_file(
+                "comment",
+                params.comment(),
+                params.comment_file(),
+                input_path,
+            ),
+        ) {
+            (Ok(title), Ok(body), Ok(comment)) => {
+                // expand the concourse build metadata variables
+                let variables = Self::build_variables();
+                let expand = |value: Option<String>| {
+                    value.map(|value| Self::expand_variables(&value, &variables))
+                };
+                // titles are single line so trim the file trailing newline
+                (
+                    expand(title).map(|title| title.trim().to_string()),
+                    expand(body),
+                    expand(comment),
+                )
+            }
+            (Err(error), _, _) | (_, Err(error), _) | (_, _, Err(error)) => {
+                return Err(error.into());
+            }
+        };
+
+        // construct an issue from the params...
+        let mut gh_issue = github_issue::Issue {
+            title: title.as_deref(),
+            labels: params.labels(),
+            assignees: params.assignees(),
+            // determine the issue number from the params (e.g. an issue created earlier in the build) or the source
+            number: Self::params_number(params.number(), params.number_file(), input_path)?
+                .or(source.number()),
+            comment: comment.as_deref(),
+            lock: params.lock(),
+            lock_reason: params.lock_reason(),
+            add_labels: params.add_labels(),
+            remove_labels: params.remove_labels(),
+            add_assignees: params.add_assignees(),
+            remove_assignees: params.remove_assignees(),
+            validate_assignees: params.validate_assignees(),
+            state: params.state(),
+            state_reason: params.state_reason(),
+            milestone: params.milestone(),
+            ..Default::default()
+        };
+        // ...and validate it before any interaction (e.g. the upsert search)
+        gh_issue
+            .validate()
+            .map_err(|error| StepError::issue("the out/put step params are invalid", error))?;
+
+        // construct the client for all interactions
+        let client = Self::client(&source, None).await.map_err(|error| {
+            StepError::issue(
+                "the out/put step was unable to construct a github client from the source values",
+                error,
+            )
+        })?;
+
+        // otherwise determine the issue number from a matching open issue if upsert requested
+        if gh_issue.number.is_none() && params.upsert() {
+            gh_issue.number = Self::upsert_number(
+                &client,
+                &source,
+                title.as_deref(),
+                params.dedupe_key(),
+            )
+            .await
+            .map_err(|error| {
+                StepError::issue(
+                    "the out/put step was unable to search for a matching github issue for upsert",
+                    error,
+                )
+            })?;
+        }
+        // determine the action...
+        let action = match gh_issue.number {
             Some(_) => github_issue::Action::Update,
             None => github_issue::Action::Create,
         };
+        // ...and append the hidden dedupe key to the body
+        let body = Self::dedupe_body(body.as_deref(), params.dedupe_key(), action);
+        gh_issue.body = body.as_deref();
+
         // ...and create the octocrab github issue
-        let issue = match gh_issue.main(action).await {
-            Ok(issue) => issue,
-            Err(error) => {
-                log::error!("{error}");
-                panic!(
+        let issue = gh_issue.main(&client, action).await.map_err(|error| {
+            StepError::issue(
+                format!(
                     "the out/put step was unable to {} the associated github issue",
                     String::from(action)
-                );
-            }
-        };
+                ),
+                error,
+            )
+        })?;
         log::info!(
             "successful {} for the github issue number {}",
             String::from(action),
             issue.number
         );
 
-        if source.number().is_none() {
+        // an updated issue is already known from the source or params
+        if matches!(action, github_issue::Action::Create) {
             // store created issue number in file for subsequent check step
             let file_path = format!("{input_path}/issue_number.txt");
             match std::fs::write(&file_path, issue.number.to_string()) {
@@ -188,25 +386,866 @@ impl concourse_resource::Resource for GithubIssue {
         }
 
         // return out step output
-        concourse_resource::OutOutput {
-            version: concourse::Version::new(issue.state.clone()),
-            metadata: Some(concourse::OutMetadata::new(
-                issue.number,
-                issue.url,
-                issue.title,
-                issue.state,
-                issue.labels,
-                issue.assignees,
-                issue.milestone,
-                issue.created_at.to_string(),
-                issue.updated_at.to_string(),
+        Ok(concourse_resource::OutOutput {
+            version: concourse::Version::from(&issue),
+            metadata: Some(Self::metadata(issue)),
+        })
+    }
+
+    // construct the github client once for all interactions within the step from the source
+    // the minimum remaining rate limit quota is only specified for the check step
+    async fn client(
+        source: &concourse::Source,
+        min_rate_limit_remaining: Option<usize>,
+    ) -> Result<github_issue::Client<'_>, github_issue::IssueError> {
+        github_issue::Client::new(github_issue::ClientConfig {
+            pat: source.pat(),
+            owner: source.owner(),
+            repo: source.repo(),
+            api_url: source.api_url(),
+            app_id: source.app_id(),
+            private_key: source.private_key(),
+            installation_id: source.installation_id(),
+            max_retries: source.max_retries(),
+            max_retry_wait: source.max_retry_wait(),
+            min_rate_limit_remaining,
+        })
+        .await
+    }
+
+    // return only the input version when the check is skipped to preserve the remaining rate limit quota
+    fn reserved_quota_versions(version: Option<concourse::Version>) -> Vec<concourse::Version> {
+        log::warn!(
+            "the github rate limit remaining is below 'min_rate_limit_remaining', and so the check step will return the input version without checking the issue"
+        );
+        version.into_iter().collect()
+    }
+
+    // report the error to concourse and exit with the error classification exit code
+    fn exit(error: StepError) -> ! {
+        eprintln!("{error}");
+        std::process::exit(error.exit_code());
+    }
+
+    // return the input version and a new version if the issue matches the trigger, and transitioned after the input version
+    async fn check_single(
+        source: &concourse::Source,
+        version: Option<concourse::Version>,
+        conditions: Option<&concourse::CompiledConditions<'_>>,
+    ) -> Result<Vec<concourse::Version>, github_issue::IssueError> {
+        // construct the client
+        let client = Self::client(source, source.min_rate_limit_remaining()).await?;
+        // retrieve the octocrab github issue and its etag
+        let (issue, etag) = match Self::check_issue(&client, source, version.as_ref()).await? {
+            octocrab::etag::Etagged {
+                etag,
+                value: Some(issue),
+            } => (issue, etag),
+            // return only the input version if the issue is unmodified since the input version
+            octocrab::etag::Etagged { value: None, .. } => {
+                log::info!(
+                    "the github issue is unmodified since the input version, and so no new version will be returned"
+                );
+                return Ok(version.into_iter().collect());
+            }
+        };
+        log::info!(
+            "the github issue information was successfully retrieved for number {}",
+            issue.number
+        );
+
+        // return only the input version if the issue does not match the compound conditions if specified...
+        let triggered = match conditions {
+            Some(conditions) => conditions.matches(&issue),
+            // ...or otherwise the trigger state and the trigger state reason if specified
+            None => {
+                issue.state == source.trigger()
+                    && source
+                        .trigger_state_reason()
+                        .is_none_or(|trigger_state_reason| {
+                            issue.state_reason.as_ref() == Some(trigger_state_reason)
+                        })
+            }
+        };
+        if !triggered {
+            log::info!(
+                "the github issue does not match the trigger, and so no new version will be returned"
+            );
+            return Ok(version.into_iter().collect());
+        }
+        // otherwise return the input version and the new version if the latter is newer
+        let mut new_version = concourse::Version::from(&issue);
+        // a reopened issue transitioned to open at its latest reopening instead of its creation
+        if issue.state == octocrab::models::IssueState::Open
+            && issue.state_reason == Some(octocrab::models::issues::IssueStateReason::Reopened)
+        {
+            let gh_issue = github_issue::Issue {
+                per_page: source.per_page(),
+                number: Some(issue.number),
+                ..Default::default()
+            };
+            if let Some(reopened) = gh_issue
+                .main_list_events(&client)
+                .await?
+                .iter()
+                .filter(|event| event.event == "reopened")
+                .map(|event| event.created_at)
+                .max()
+            {
+                new_version = concourse::Version::new(
+                    issue.state.clone(),
+                    issue.number,
+                    reopened.to_rfc3339(),
+                );
+            }
+        }
+        let new_version = new_version.with_etag(etag.map(|etag| etag.to_string()));
+        Ok(match version {
+            Some(version) if !new_version.is_newer(&version) => vec![version],
+            Some(version) => vec![version, new_version],
+            None => vec![new_version],
+        })
+    }
+
+    // retrieve the github issue specified by the source number conditionally on the input version etag, or else filtered from a list with the source parameters
+    async fn check_issue(
+        client: &github_issue::Client<'_>,
+        source: &concourse::Source,
+        version: Option<&concourse::Version>,
+    ) -> Result<octocrab::etag::Etagged<octocrab::models::issues::Issue>, github_issue::IssueError>
+    {
+        // the issue list does not support conditional requests
+        let Some(number) = source.number() else {
+            let issue = Self::source_issue(client, source, None).await?;
+            return Ok(octocrab::etag::Etagged {
+                etag: None,
+                value: Some(issue),
+            });
+        };
+        // the input version etag is only valid for the same issue
+        // versions (and therefore etags) are only stored after the issue matches the trigger, so the read is unconditional until then
+        let etag = version
+            .filter(|version| version.number() == Some(number))
+            .and_then(|version| version.etag());
+        // construct an issue...
+        let gh_issue = github_issue::Issue {
+            number: Some(number),
+            etag,
+            ..Default::default()
+        };
+        // ...and return the octocrab github issue if modified since the etag
+        gh_issue.main_read_etagged(client).await
+    }
+
+    // retrieve the github issue specified by the number, or else filtered from a list with the source parameters
+    async fn source_issue(
+        client: &github_issue::Client<'_>,
+        source: &concourse::Source,
+        number: Option<u64>,
+    ) -> Result<octocrab::models::issues::Issue, github_issue::IssueError> {
+        // construct an issue...
+        let gh_issue = github_issue::Issue {
+            labels: source.labels(),
+            assignees: source.assignee(),
+            creator: source.creator(),
+            per_page: source.per_page(),
+            number,
+            state: source.state(),
+            milestone: source.milestone(),
+            ..Default::default()
+        };
+        // ...determine the action...
+        let action = match number {
+            Some(_) => github_issue::Action::Read,
+            None => github_issue::Action::List,
+        };
+        // ...and return the octocrab github issue
+        gh_issue.main(client, action).await
+    }
+
+    // return the input version and a version for each issue filtered from a list with the source parameters, and created or updated after the input version
+    async fn check_multiple(
+        source: &concourse::Source,
+        version: Option<concourse::Version>,
+        conditions: Option<&concourse::CompiledConditions<'_>>,
+    ) -> Result<Vec<concourse::Version>, github_issue::IssueError> {
+        // construct the client and an issue with the input version as cursor...
+        let client = Self::client(source, source.min_rate_limit_remaining()).await?;
+        let gh_issue = github_issue::Issue {
+            labels: source.labels(),
+            assignees: source.assignee(),
+            creator: source.creator(),
+            since: version.as_ref().and_then(|version| version.updated()),
+            per_page: source.per_page(),
+            max_issues: source.max_issues(),
+            state: source.state(),
+            milestone: source.milestone(),
+            ..Default::default()
+        };
+        // ...and return the octocrab github issues
+        let issues = gh_issue.main_list(&client).await?;
+        log::info!(
+            "{} github issues were successfully retrieved from the filtered list",
+            issues.len()
+        );
+
+        // convert issues (but not pull requests) to versions after the input version
+        let new_versions = issues
+            .iter()
+            .filter(|issue| issue.pull_request.is_none())
+            .filter(|issue| conditions.is_none_or(|conditions| conditions.matches(issue)))
+            .map(|issue| {
+                concourse::Version::new(
+                    issue.state.clone(),
+                    issue.number,
+                    issue.updated_at.to_rfc3339(),
+                )
+            })
+            .collect::<Vec<concourse::Version>>();
+
+        Ok(Self::updated_versions(version, new_versions))
+    }
+
+    // return the input version and a version for each comment on the issue matching the author and pattern, and created after the input version
+    async fn check_comments(
+        source: &concourse::Source,
+        number: u64,
+        version: Option<concourse::Version>,
+        pattern: Option<&regex::Regex>,
+    ) -> Result<Vec<concourse::Version>, github_issue::IssueError> {
+        // construct the client, and retrieve the issue for its state
+        let client = Self::client(source, source.min_rate_limit_remaining()).await?;
+        let issue = Self::source_issue(&client, source, Some(number)).await?;
+        // the input version is only a cursor if it is for a comment on the same issue
+        let cursor = version
+            .as_ref()
+            .filter(|version| version.number() == Some(number))
+            .and_then(|version| {
+                version
+                    .comment()
+                    .map(|comment| (comment, version.updated().map(String::from)))
+            });
+        // construct an issue with the input version as cursor...
+        let gh_issue = github_issue::Issue {
+            since: cursor.as_ref().and_then(|(_, updated)| updated.as_deref()),
+            per_page: source.per_page(),
+            number: Some(number),
+            ..Default::default()
+        };
+        // ...and return the octocrab github issue comments
+        let comments = gh_issue.main_list_comments(&client).await?;
+        log::info!(
+            "{} github issue comments were successfully retrieved",
+            comments.len()
+        );
+
+        // convert comments matching the author and pattern to versions after the cursor
+        let new_versions = Self::comment_versions(
+            source,
+            &comments,
+            &issue.state,
+            number,
+            pattern,
+            cursor.as_ref().map(|(comment, _)| *comment),
+        );
+
+        // slash commands are only accepted from users with write, maintain, or admin permission
+        let mut authors = new_versions
+            .iter()
+            .filter_map(|(_, author)| *author)
+            .collect::<Vec<&str>>();
+        authors.sort_unstable();
+        authors.dedup();
+        let writers = match authors.is_empty() {
+            true => Vec::new(),
+            false => gh_issue.main_writers(&client, &authors).await?,
+        };
+        let new_versions = Self::writer_versions(new_versions, &writers);
+
+        Ok(Self::cursor_versions(
+            version,
+            cursor.is_some(),
+            new_versions,
+        ))
+    }
+
+    // return the input version and a version for each event on the issue matching the types and label, and occurring after the input version
+    async fn check_events(
+        source: &concourse::Source,
+        number: u64,
+        version: Option<concourse::Version>,
+    ) -> Result<Vec<concourse::Version>, github_issue::IssueError> {
+        // construct the client, and retrieve the issue for its state
+        let client = Self::client(source, source.min_rate_limit_remaining()).await?;
+        let issue = Self::source_issue(&client, source, Some(number)).await?;
+        // the input version is only a cursor if it is for an event on the same issue
+        let cursor = version
+            .as_ref()
+            .filter(|version| version.number() == Some(number))
+            .and_then(concourse::Version::event);
+        // construct an issue for listing events...
+        let gh_issue = github_issue::Issue {
+            per_page: source.per_page(),
+            number: Some(number),
+            ..Default::default()
+        };
+        // ...and return the github issue events
+        let events = gh_issue.main_list_events(&client).await?;
+        log::info!(
+            "{} github issue events were successfully retrieved",
+            events.len()
+        );
+
+        // convert events matching the types and label to versions after the cursor
+        let new_versions = Self::event_versions(source, events, &issue.state, number, cursor);
+
+        Ok(Self::cursor_versions(
+            version,
+            cursor.is_some(),
+            new_versions,
+        ))
+    }
+
+    // return the input version and a new version if the count of the reaction content reaches the threshold or drops below it after reaching it
+    async fn check_reactions(
+        source: &concourse::Source,
+        number: u64,
+        version: Option<concourse::Version>,
+    ) -> Result<Vec<concourse::Version>, github_issue::IssueError> {
+        // construct the client, and retrieve the issue for its state
+        let client = Self::client(source, source.min_rate_limit_remaining()).await?;
+        let issue = Self::source_issue(&client, source, Some(number)).await?;
+        // construct an issue for listing reactions...
+        let gh_issue = github_issue::Issue {
+            per_page: source.per_page(),
+            number: Some(number),
+            ..Default::default()
+        };
+        // ...and return the octocrab github issue reactions
+        let reactions = gh_issue.main_list_reactions(&client).await?;
+        log::info!(
+            "{} github issue reactions were successfully retrieved",
+            reactions.len()
+        );
+        // the members of the team if only their reactions are counted
+        let members = match source.reaction_team() {
+            Some(team) => Some(gh_issue.main_team_members(&client, team).await?),
+            None => None,
+        };
+
+        Ok(Self::reaction_versions(
+            source,
+            reactions,
+            members.as_deref(),
+            &issue.state,
+            number,
+            version,
+        ))
+    }
+
+    // convert the comments matching the author and pattern (and only slash commands with their authors if commands requested) to versions ordered by comment id
+    // the input version cursor is already processed, so only later comments are converted (comment ids are sequential)
+    fn comment_versions<'comment>(
+        source: &concourse::Source,
+        comments: &'comment [octocrab::models::issues::Comment],
+        state: &octocrab::models::IssueState,
+        number: u64,
+        pattern: Option<&regex::Regex>,
+        cursor: Option<u64>,
+    ) -> Vec<(concourse::Version, Option<&'comment str>)> {
+        let mut new_versions = comments
+            .iter()
+            .filter(|comment| cursor.is_none_or(|cursor| comment.id.into_inner() > cursor))
+            .filter(|comment| {
+                source
+                    .comment_author()
+                    .is_none_or(|author| comment.user.login.eq_ignore_ascii_case(author))
+            })
+            .filter(|comment| {
+                pattern.is_none_or(|pattern| {
+                    pattern.is_match(comment.body.as_deref().unwrap_or_default())
+                })
+            })
+            .filter_map(|comment| {
+                let version =
+                    concourse::Version::new(state.clone(), number, comment.created_at.to_rfc3339())
+                        .with_comment(comment.id.into_inner());
+                match source.commands() {
+                    Some(commands) => {
+                        Self::parse_command(comment.body.as_deref().unwrap_or_default(), commands)
+                            .map(|(command, arguments)| {
+                                (
+                                    version.with_command(command, arguments),
+                                    Some(comment.user.login.as_str()),
+                                )
+                            })
+                    }
+                    None => Some((version, None)),
+                }
+            })
+            .collect::<Vec<(concourse::Version, Option<&str>)>>();
+        new_versions.sort_by_key(|(new_version, _)| new_version.comment());
+
+        new_versions
+    }
+
+    // drop the versions for slash commands whose authors do not have write permission
+    fn writer_versions(
+        new_versions: Vec<(concourse::Version, Option<&str>)>,
+        writers: &[String],
+    ) -> Vec<concourse::Version> {
+        new_versions
+            .into_iter()
+            .filter_map(|(new_version, author)| match author {
+                Some(author) if !writers.iter().any(|writer| writer == author) => {
+                    log::warn!(
+                        "the command in comment {} is ignored because {author} does not have write permission on the repository",
+                        new_version.comment().unwrap_or_default()
+                    );
+                    None
+                }
+                _ => Some(new_version),
+            })
+            .collect()
+    }
+
+    // convert the events matching the types and label to versions ordered by event id
+    // the input version cursor is already processed, so only later events are converted (event ids are sequential)
+    fn event_versions(
+        source: &concourse::Source,
+        events: Vec<github_issue::IssueEvent>,
+        state: &octocrab::models::IssueState,
+        number: u64,
+        cursor: Option<u64>,
+    ) -> Vec<concourse::Version> {
+        let event_types = source.events().unwrap_or_default();
+        let mut new_versions = events
+            .into_iter()
+            .filter(|event| event_types.contains(&event.event))
+            .filter(|event| {
+                source.event_label().is_none_or(|event_label| {
+                    event
+                        .label
+                        .as_ref()
+                        .is_some_and(|label| label.name.eq_ignore_ascii_case(event_label))
+                })
+            })
+            .filter(|event| cursor.is_none_or(|cursor| event.id > cursor))
+            .map(|event| {
+                concourse::Version::new(state.clone(), number, event.created_at.to_rfc3339())
+                    .with_event(event.id, event.event, event.actor.map(|actor| actor.login))
+            })
+            .collect::<Vec<concourse::Version>>();
+        new_versions.sort_by_key(concourse::Version::event);
+
+        new_versions
+    }
+
+    // return the input version and the new versions updated at or after it ordered by last updated time, or otherwise only the latest new version for the first check
+    // the input version is also listed because the list is since its update time, and so it is not repeated
+    fn updated_versions(
+        version: Option<concourse::Version>,
+        mut new_versions: Vec<concourse::Version>,
+    ) -> Vec<concourse::Version> {
+        new_versions.sort_by(|first, second| first.updated().cmp(&second.updated()));
+        match version {
+            Some(version) => {
+                new_versions.retain(|new_version| {
+                    *new_version != version && new_version.updated() >= version.updated()
+                });
+                new_versions.insert(0, version);
+                new_versions
+            }
+            None => new_versions.pop().into_iter().collect(),
+        }
+    }
+
+    // return the input version and the new versions after its cursor, or otherwise only the latest new version for the first check
+    fn cursor_versions(
+        version: Option<concourse::Version>,
+        cursor: bool,
+        mut new_versions: Vec<concourse::Version>,
+    ) -> Vec<concourse::Version> {
+        match (cursor, version) {
+            (true, Some(version)) => {
+                new_versions.insert(0, version);
+                new_versions
+            }
+            _ => new_versions.pop().into_iter().collect(),
+        }
+    }
+
+    // return the input version and a new version if the count of the reaction content (from team members if specified) reaches the threshold, and the input version had not reached it
+    // the input version reached the threshold if it contains the reaction which reached it, and so a new version without that reaction is returned if the count drops below the threshold
+    // each crossing is therefore recorded, and is stable while the count remains at or above the threshold
+    fn reaction_versions(
+        source: &concourse::Source,
+        reactions: Vec<octocrab::models::reactions::Reaction>,
+        members: Option<&[String]>,
+        state: &octocrab::models::IssueState,
+        number: u64,
+        version: Option<concourse::Version>,
+    ) -> Vec<concourse::Version> {
+        // reactions with the content from team members (if specified) in order of creation
+        let mut reactions = reactions
+            .into_iter()
+            .filter(|candidate| source.reaction() == Some(&candidate.content))
+            .filter(|candidate| {
+                members.is_none_or(|members| {
+                    members
+                        .iter()
+                        .any(|member| member.eq_ignore_ascii_case(&candidate.user.login))
+                })
+            })
+            .collect::<Vec<octocrab::models::reactions::Reaction>>();
+        reactions.sort_by_key(|candidate| (candidate.created_at, candidate.id));
+
+        // whether the input version reached the threshold for the issue
+        let threshold = source.reaction_threshold();
+        let reached = version.as_ref().is_some_and(|version| {
+            version.number() == Some(number)
+                && version.reaction().is_some()
+                && version
+                    .reactions()
+                    .is_some_and(|reactions| reactions >= threshold)
+        });
+        let Some(crossing) = reactions.get(threshold.saturating_sub(1)) else {
+            // return the input version and a new version for the count below the threshold if the input version reached it
+            if reached {
+                log::info!(
+                    "the github issue reaction count {} dropped below the threshold {threshold}, and so a new version without the threshold reaction will be returned",
+                    reactions.len()
+                );
+                let new_version =
+                    concourse::Version::new(state.clone(), number, chrono::Utc::now().to_rfc3339())
+                        .with_reactions(None, reactions.len());
+                return version.into_iter().chain([new_version]).collect();
+            }
+            // otherwise return only the input version
+            log::info!(
+                "the github issue reaction count {} is below the threshold {threshold}, and so no new version will be returned",
+                reactions.len()
+            );
+            return version.into_iter().collect();
+        };
+        // return only the input version if it already reached the threshold
+        if reached {
+            log::info!(
+                "the github issue reaction count {} remains at or above the threshold {threshold}, and so no new version will be returned",
+                reactions.len()
+            );
+            return version.into_iter().collect();
+        }
+        // otherwise return the input version and the new version for the reaction which reached the threshold
+        let new_version =
+            concourse::Version::new(state.clone(), number, crossing.created_at.to_rfc3339())
+                .with_reactions(Some(crossing.id.into_inner()), reactions.len());
+
+        version.into_iter().chain([new_version]).collect()
+    }
+
+    // parse the slash command and its arguments from the first line of the comment body if it is an allowed command
+    fn parse_command(body: &str, commands: &[String]) -> Option<(String, String)> {
+        let line = body.trim_start().lines().next()?.strip_prefix('/')?;
+        let (command, arguments) = match line.split_once(char::is_whitespace) {
+            Some((command, arguments)) => (command, arguments.trim()),
+            None => (line.trim_end(), ""),
+        };
+        // allowed commands may be specified with or without the leading slash
+        commands
+            .iter()
+            .any(|allowed| allowed.trim_start_matches('/') == command)
+            .then(|| (command.to_string(), arguments.to_string()))
+    }
+
+    // return the number of an open issue within the source filters matching the dedupe key if specified, and otherwise matching the exact title
+    async fn upsert_number(
+        client: &github_issue::Client<'_>,
+        source: &concourse::Source,
+        title: Option<&str>,
+        dedupe_key: Option<&str>,
+    ) -> Result<Option<u64>, github_issue::IssueError> {
+        // construct an issue for listing the open issues of the source filters...
+        // the search api is not used because a newly created issue is only searchable after indexing, and so a duplicate could be created
+        let gh_issue = github_issue::Issue {
+            labels: source.labels(),
+            assignees: source.assignee(),
+            creator: source.creator(),
+            per_page: source.per_page(),
+            max_issues: source.max_issues(),
+            state: Some("open"),
+            milestone: source.milestone(),
+            ..Default::default()
+        };
+        // ...and return the octocrab github issues
+        let issues = gh_issue.main_list(client).await?;
+
+        // find the matching issue (but not pull request)
+        let mut issues = issues.iter().filter(|issue| issue.pull_request.is_none());
+        let issue = match (dedupe_key, title) {
+            (Some(dedupe_key), _) => {
+                let marker = Self::dedupe_marker(dedupe_key);
+                issues.find(|issue| {
+                    issue
+                        .body
+                        .as_deref()
+                        .is_some_and(|body| body.contains(&marker))
+                })
+            }
+            (None, Some(title)) => issues.find(|issue| issue.title == title),
+            (None, None) => None,
+        };
+        match issue {
+            Some(issue) => log::info!(
+                "the open github issue number {} matches, and will be updated",
+                issue.number
+            ),
+            None => log::info!("no open github issue matches, and a new issue will be created"),
+        }
+
+        Ok(issue.map(|issue| issue.number))
+    }
+
+    // return the inline param value, or else the contents of the param file relative to the input path
+    fn param_or_file(
+        name: &str,
+        value: Option<&str>,
+        file: Option<&str>,
+        input_path: &str,
+    ) -> Result<Option<String>, String> {
+        match (value, file) {
+            (Some(_), Some(_)) => Err(format!(
+                "only one of the '{name}' and '{name}_file' params may be specified"
             )),
+            (Some(value), None) => Ok(Some(String::from(value))),
+            (None, Some(file)) => {
+                let file_path = std::path::Path::new(input_path).join(file);
+                match std::fs::read_to_string(&file_path) {
+                    Ok(contents) => Ok(Some(contents)),
+                    Err(error) => Err(format!(
+                        "the '{name}_file' at {} could not be read: {error}",
+                        file_path.display()
+                    )),
+                }
+            }
+            (None, None) => Ok(None),
         }
     }
-}
 
-// helper functions if we need them
-impl GithubIssue {}
+    // return the inline issue number param, or else the issue number in the param file relative to the input path
+    fn params_number(
+        number: Option<u64>,
+        file: Option<&str>,
+        input_path: &str,
+    ) -> Result<Option<u64>, String> {
+        let number = number.map(|number| number.to_string());
+        match Self::param_or_file("number", number.as_deref(), file, input_path)? {
+            // the number file has a trailing newline if written by e.g. a task
+            Some(number) => match number.trim().parse() {
+                Ok(number) => Ok(Some(number)),
+                Err(error) => Err(format!(
+                    "the issue number '{}' is invalid: {error}",
+                    number.trim()
+                )),
+            },
+            None => Ok(None),
+        }
+    }
+
+    // return the concourse build metadata variables available to the out/put step, and the build url derived from them
+    fn build_variables() -> Vec<(&'static str, String)> {
+        let mut variables = [
+            "BUILD_ID",
+            "BUILD_NAME",
+            "BUILD_JOB_NAME",
+            "BUILD_PIPELINE_NAME",
+            "BUILD_PIPELINE_INSTANCE_VARS",
+            "BUILD_TEAM_NAME",
+            "ATC_EXTERNAL_URL",
+        ]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok().map(|value| (name, value)))
+        .collect::<Vec<(&'static str, String)>>();
+        if let Some(build_url) = Self::build_url(&variables) {
+            variables.push(("BUILD_URL", build_url));
+        }
+
+        variables
+    }
+
+    // construct the url of the build from the concourse build metadata variables
+    fn build_url(variables: &[(&str, String)]) -> Option<String> {
+        let variable = |name: &str| {
+            variables
+                .iter()
+                .find(|(variable_name, _)| *variable_name == name)
+                .map(|(_, value)| value.as_str())
+        };
+        let atc_external_url = variable("ATC_EXTERNAL_URL")?.trim_end_matches('/');
+        // pipeline job build url, unless the pipeline is an instance which requires instance vars in the url
+        if let (Some(team), Some(pipeline), Some(job), Some(build), None) = (
+            variable("BUILD_TEAM_NAME"),
+            variable("BUILD_PIPELINE_NAME"),
+            variable("BUILD_JOB_NAME"),
+            variable("BUILD_NAME"),
+            variable("BUILD_PIPELINE_INSTANCE_VARS"),
+        ) {
+            return Some(format!(
+                "{atc_external_url}/teams/{team}/pipelines/{pipeline}/jobs/{job}/builds/{build}"
+            ));
+        }
+        // otherwise the build id url is valid for all builds
+        Some(format!(
+            "{atc_external_url}/builds/{}",
+            variable("BUILD_ID")?
+        ))
+    }
+
+    // expand ${NAME} variables within the text, and retain unknown variables unexpanded
+    fn expand_variables(text: &str, variables: &[(&str, String)]) -> String {
+        variables
+            .iter()
+            .fold(String::from(text), |text, (name, value)| {
+                text.replace(&format!("${{{name}}}"), value)
+            })
+    }
+
+    // hidden marker within an issue body for matching during upsert
+    fn dedupe_marker(dedupe_key: &str) -> String {
+        format!("<!-- concourse-github-issue dedupe_key: {dedupe_key} -->")
+    }
+
+    // append the hidden dedupe marker to the issue body if a dedupe key is specified
+    // the marker alone is only the body of a created issue so that an update never replaces an existing body that was not specified
+    fn dedupe_body(
+        body: Option<&str>,
+        dedupe_key: Option<&str>,
+        action: github_issue::Action,
+    ) -> Option<String> {
+        match (body, dedupe_key) {
+            (Some(body), Some(dedupe_key)) => {
+                Some(format!("{body}\n\n{}", Self::dedupe_marker(dedupe_key)))
+            }
+            (None, Some(dedupe_key)) if matches!(action, github_issue::Action::Create) => {
+                Some(Self::dedupe_marker(dedupe_key))
+            }
+            (body, _) => body.map(String::from),
+        }
+    }
+
+    // write the github issue information to files within the output directory
+    fn write_issue_files(
+        issue: &octocrab::models::issues::Issue,
+        output_path: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let output_path = std::path::Path::new(output_path);
+        // labels are written as newline delimited names
+        let labels = issue
+            .labels
+            .iter()
+            .map(|label| label.name.as_str())
+            .collect::<Vec<&str>>()
+            .join("\n");
+        let files = [
+            ("issue.json", serde_json::to_string_pretty(issue)?),
+            ("number", issue.number.to_string()),
+            ("title", issue.title.clone()),
+            ("body.md", issue.body.clone().unwrap_or_default()),
+            (
+                "state",
+                serde_json::to_value(&issue.state)?
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+            ("labels", labels),
+            ("url", issue.html_url.to_string()),
+        ];
+        for (file_name, contents) in files {
+            let file_path = output_path.join(file_name);
+            if let Err(error) = std::fs::write(&file_path, contents) {
+                log::error!(
+                    "the issue information could not be written to {}",
+                    file_path.display()
+                );
+                return Err(error.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    // write the slash command and its arguments from the version for command triggers to files within the output directory
+    fn write_command_files(
+        version: &concourse::Version,
+        output_path: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(command) = version.command() else {
+            return Ok(());
+        };
+        let output_path = std::path::Path::new(output_path);
+        let files = [
+            ("command", command),
+            ("arguments", version.arguments().unwrap_or_default()),
+        ];
+        for (file_name, contents) in files {
+            let file_path = output_path.join(file_name);
+            if let Err(error) = std::fs::write(&file_path, contents) {
+                log::error!(
+                    "the command information could not be written to {}",
+                    file_path.display()
+                );
+                return Err(error.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    // write the reaction count, and the id of the reaction which reached the threshold, from the version for reaction triggers to files within the output directory
+    // the reaction file is absent for a version when the count dropped below the threshold
+    fn write_reaction_files(
+        version: &concourse::Version,
+        output_path: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(reactions) = version.reactions() else {
+            return Ok(());
+        };
+        let output_path = std::path::Path::new(output_path);
+        let mut files = vec![("reactions", reactions.to_string())];
+        if let Some(reaction) = version.reaction() {
+            files.push(("reaction", reaction.to_string()));
+        }
+        for (file_name, contents) in files {
+            let file_path = output_path.join(file_name);
+            if let Err(error) = std::fs::write(&file_path, contents) {
+                log::error!(
+                    "the reaction information could not be written to {}",
+                    file_path.display()
+                );
+                return Err(error.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    // construct the concourse metadata from the github issue
+    fn metadata(issue: octocrab::models::issues::Issue) -> concourse::OutMetadata {
+        concourse::OutMetadata::new(
+            issue.number,
+            issue.url,
+            issue.title,
+            issue.state,
+            issue.state_reason,
+            issue.labels,
+            issue.assignees,
+            issue.milestone,
+            issue.created_at.to_string(),
+            issue.updated_at.to_string(),
+        )
+    }
+}
 // macro to populate the concourse functions
 concourse_resource::create_resource!(GithubIssue);
 
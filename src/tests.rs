@@ -1,7 +1,12 @@
 use super::*;
 
-#[test]
-fn test_resource_check_read() {
+// deserialize the source from the concourse pipeline json input
+fn source(source_input: &str) -> concourse::Source {
+    serde_json::from_str(source_input).expect("source could not be deserialized")
+}
+
+#[tokio::test]
+async fn test_resource_check_read() {
     // validate basic check reading from mitodl/ol-infrastructure issue 1
     // concourse pipeline json input
     let source_input = r#"
@@ -12,30 +17,246 @@ fn test_resource_check_read() {
 }"#;
     let version_input = r#"
 {
-    "state": "closed"
+    "state": "closed",
+    "number": "1",
+    "updated": "2000-01-01T00:00:00+00:00"
 }"#;
     // deserialize version and source for inputs
-    let source =
-        serde_json::from_str::<<GithubIssue as concourse_resource::Resource>::Source>(source_input)
-            .expect("source could not be deserialized");
+    let source = source(source_input);
     let version = serde_json::from_str::<<GithubIssue as concourse_resource::Resource>::Version>(
         version_input,
     )
     .expect("version could not be deserialized");
-    let version_vec = GithubIssue::resource_check(Some(source), Some(version));
-    // the issue is closed so we expect a size two vec
+    let version_vec = GithubIssue::check(Some(source), Some(version.clone()))
+        .await
+        .expect("the check step returned an error");
+    // the issue was closed after the input version so we expect the input and new versions
     assert_eq!(
-        version_vec,
+        version_vec.len(),
+        2,
+        "the resource_check did not return a two size vector of versions for a closed issue",
+    );
+    assert_eq!(
+        version_vec[0], version,
+        "the resource_check did not return the input version first",
+    );
+    assert_eq!(
+        version_vec[1].number(),
+        Some(1),
+        "the resource_check did not return a new version for the issue number",
+    );
+    assert!(
+        version_vec[1].etag().is_some(),
+        "the resource_check did not return a new version with the issue etag",
+    );
+}
+
+// reopened issue 5 in octocat/hello-world which was edited after its reopening
+const REOPENED_ISSUE: &str = r#"
+{
+    "id": 1,
+    "node_id": "MDU6SXNzdWUx",
+    "url": "https://api.github.com/repos/octocat/hello-world/issues/5",
+    "repository_url": "https://api.github.com/repos/octocat/hello-world",
+    "labels_url": "https://api.github.com/repos/octocat/hello-world/issues/5/labels{/name}",
+    "comments_url": "https://api.github.com/repos/octocat/hello-world/issues/5/comments",
+    "events_url": "https://api.github.com/repos/octocat/hello-world/issues/5/events",
+    "html_url": "https://github.com/octocat/hello-world/issues/5",
+    "number": 5,
+    "state": "open",
+    "state_reason": "reopened",
+    "title": "Release 1.4.0",
+    "user": {
+        "login": "octocat",
+        "id": 1,
+        "node_id": "MDQ6VXNlcjE=",
+        "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+        "gravatar_id": "",
+        "url": "https://api.github.com/users/octocat",
+        "html_url": "https://github.com/octocat",
+        "followers_url": "https://api.github.com/users/octocat/followers",
+        "following_url": "https://api.github.com/users/octocat/following{/other_user}",
+        "gists_url": "https://api.github.com/users/octocat/gists{/gist_id}",
+        "starred_url": "https://api.github.com/users/octocat/starred{/owner}{/repo}",
+        "subscriptions_url": "https://api.github.com/users/octocat/subscriptions",
+        "organizations_url": "https://api.github.com/users/octocat/orgs",
+        "repos_url": "https://api.github.com/users/octocat/repos",
+        "events_url": "https://api.github.com/users/octocat/events{/privacy}",
+        "received_events_url": "https://api.github.com/users/octocat/received_events",
+        "type": "User",
+        "site_admin": false
+    },
+    "labels": [],
+    "assignees": [],
+    "locked": false,
+    "comments": 0,
+    "created_at": "2024-01-01T00:00:00Z",
+    "updated_at": "2024-01-09T00:00:00Z"
+}"#;
+
+#[tokio::test]
+async fn test_resource_check_reopened() {
+    // validate the open trigger version of a reopened issue is for its latest reopening instead of its edits
+    let github = github_issue::tests::mock_github(vec![
+        (
+            "/repos/octocat/hello-world/issues/5/events",
+            200,
+            r#"[
+                {"id": 1, "event": "closed", "actor": null, "created_at": "2024-01-02T00:00:00Z"},
+                {"id": 2, "event": "reopened", "actor": null, "created_at": "2024-01-03T00:00:00Z"},
+                {"id": 3, "event": "closed", "actor": null, "created_at": "2024-01-04T00:00:00Z"},
+                {"id": 4, "event": "reopened", "actor": null, "created_at": "2024-01-05T00:00:00Z"}
+            ]"#,
+        ),
+        ("/repos/octocat/hello-world/issues/5", 200, REOPENED_ISSUE),
+    ])
+    .await;
+    let source = source(
+        &serde_json::json!({
+            "owner": "octocat",
+            "repo": "hello-world",
+            "api_url": github.api_url,
+            "number": 5,
+            "trigger": "open"
+        })
+        .to_string(),
+    );
+    let version_vec = GithubIssue::check(Some(source), None)
+        .await
+        .expect("the check step returned an error");
+    assert_eq!(
+        version_vec.len(),
+        1,
+        "the resource_check did not return a version for the reopened issue",
+    );
+    assert_eq!(
+        version_vec[0].updated(),
+        Some("2024-01-05T00:00:00+00:00"),
+        "the resource_check did not return a version for the latest reopening",
+    );
+}
+
+#[tokio::test]
+async fn test_resource_check_unmodified() {
+    // validate the check returns only the input version when the issue is unmodified since its etag
+    let github =
+        github_issue::tests::mock_github(vec![("/repos/octocat/hello-world/issues/5", 304, "")])
+            .await;
+    let source = source(
+        &serde_json::json!({
+            "owner": "octocat",
+            "repo": "hello-world",
+            "api_url": github.api_url,
+            "number": 5
+        })
+        .to_string(),
+    );
+    let version = concourse::Version::new(
+        octocrab::models::IssueState::Closed,
+        5,
+        "2024-01-04T00:00:00+00:00",
+    )
+    .with_etag(Some(String::from(r#""abc123""#)));
+    assert_eq!(
+        GithubIssue::check(Some(source), Some(version.clone()))
+            .await
+            .expect("the check step returned an error"),
+        vec![version],
+        "the check did not return only the input version for the unmodified issue",
+    );
+    assert_eq!(
+        github.requests(),
         vec![
-            concourse::Version::new(octocrab::models::IssueState::Open),
-            concourse::Version::new(octocrab::models::IssueState::Closed)
+            String::from("GET /rate_limit"),
+            String::from("GET /repos/octocat/hello-world/issues/5"),
         ],
-        "the resource_check did not return a two size vector of issue states for a closed issue",
+        "the check did not read the issue once",
     );
 }
 
-#[test]
-fn test_resource_check_list() {
+// octocrab rate limit json with the remaining core quota
+fn rate_limit(remaining: usize) -> String {
+    let rate = serde_json::json!({
+        "limit": 5000,
+        "used": 5000 - remaining,
+        "remaining": remaining,
+        "reset": 1_700_000_000
+    });
+    serde_json::json!({
+        "resources": {"core": rate, "search": rate},
+        "rate": rate
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_resource_check_reserved_quota() {
+    // validate the check returns the input version when the remaining quota is below the minimum
+    let version = concourse::Version::new(
+        octocrab::models::IssueState::Open,
+        5,
+        String::from("2024-01-01T00:00:00+00:00"),
+    );
+    let check_source = |api_url: &str| {
+        source(
+            &serde_json::json!({
+                "owner": "octocat",
+                "repo": "hello-world",
+                "api_url": api_url,
+                "number": 5,
+                "comments": true,
+                "min_rate_limit_remaining": 100
+            })
+            .to_string(),
+        )
+    };
+    // the remaining quota of the rate limit endpoint
+    let github =
+        github_issue::tests::mock_github(vec![("/rate_limit", 200, &rate_limit(10))]).await;
+    assert_eq!(
+        GithubIssue::check(Some(check_source(&github.api_url)), Some(version.clone()))
+            .await
+            .expect("the check step returned an error"),
+        vec![version.clone()],
+        "the check did not return the input version for the rate limit endpoint quota below the minimum",
+    );
+    assert_eq!(
+        github.requests(),
+        vec![String::from("GET /rate_limit")],
+        "the check interacted with the issue although the quota was reserved",
+    );
+    // the remaining quota of the response headers during the check
+    let github = github_issue::tests::mock_github_headers(vec![
+        ("/rate_limit", 200, Vec::new(), &rate_limit(5000)),
+        (
+            "/repos/octocat/hello-world/issues/5",
+            200,
+            vec![
+                ("x-ratelimit-remaining", "10"),
+                ("x-ratelimit-limit", "5000"),
+            ],
+            REOPENED_ISSUE,
+        ),
+    ])
+    .await;
+    assert_eq!(
+        GithubIssue::check(Some(check_source(&github.api_url)), Some(version.clone()))
+            .await
+            .expect("the check step returned an error"),
+        vec![version],
+        "the check did not return the input version for the response header quota below the minimum",
+    );
+    assert!(
+        !github
+            .requests()
+            .iter()
+            .any(|request| request.contains("/comments")),
+        "the check listed the comments although the quota was reserved",
+    );
+}
+
+#[tokio::test]
+async fn test_resource_check_list() {
     // validate basic check listing from mitodl/ol-infrastructure and filtering to issue 833
     // concourse pipeline json input
     let source_input = r#"
@@ -53,21 +274,57 @@ fn test_resource_check_list() {
     "state": "closed"
 }"#;
     // deserialize version and source for inputs
-    let source =
-        serde_json::from_str::<<GithubIssue as concourse_resource::Resource>::Source>(source_input)
-            .expect("source could not be deserialized");
+    let source = source(source_input);
     let version = serde_json::from_str::<<GithubIssue as concourse_resource::Resource>::Version>(
         version_input,
     )
     .expect("version could not be deserialized");
-    let version_vec = GithubIssue::resource_check(Some(source), Some(version));
-    // the issue is closed and trigger is open so we expect a size one vec
+    let version_vec = GithubIssue::check(Some(source), Some(version.clone()))
+        .await
+        .expect("the check step returned an error");
+    // the issue is closed and trigger is open so we expect only the input version
     assert_eq!(
         version_vec,
-        vec![concourse::Version::new(
-            octocrab::models::IssueState::Closed
-        )],
-        "the resource_check did not return a one size vector of issue states for an issue with differing trigger and state",
+        vec![version],
+        "the resource_check did not return only the input version for an issue with differing trigger and state",
+    );
+}
+
+#[tokio::test]
+async fn test_resource_check_multiple() {
+    // validate check listing from mitodl/ol-infrastructure with a version per issue
+    // concourse pipeline json input
+    let source_input = r#"
+{
+    "owner": "mitodl",
+    "repo": "ol-infrastructure",
+    "creator": "blarghmatey",
+    "state": "closed",
+    "multiple": true
+}"#;
+    let version_input = r#"
+{
+    "state": "closed",
+    "number": "1",
+    "updated": "2000-01-01T00:00:00+00:00"
+}"#;
+    // deserialize version and source for inputs
+    let source = source(source_input);
+    let version = serde_json::from_str::<<GithubIssue as concourse_resource::Resource>::Version>(
+        version_input,
+    )
+    .expect("version could not be deserialized");
+    let version_vec = GithubIssue::check(Some(source), Some(version.clone()))
+        .await
+        .expect("the check step returned an error");
+    // multiple issues were updated after the input version so we expect the input version and multiple new versions
+    assert!(
+        version_vec.len() > 2,
+        "the resource_check did not return a version for each issue updated after the input version",
+    );
+    assert_eq!(
+        version_vec[0], version,
+        "the resource_check did not return the input version first",
     );
 }
 
@@ -86,30 +343,22 @@ fn test_resource_check_skip() {
     "state": "open"
 }"#;
     // deserialize version and source for inputs
-    let source =
-        serde_json::from_str::<<GithubIssue as concourse_resource::Resource>::Source>(source_input)
-            .expect("source could not be deserialized");
+    let source = source(source_input);
     let version = serde_json::from_str::<<GithubIssue as concourse_resource::Resource>::Version>(
         version_input,
     )
     .expect("version could not be deserialized");
-    let version_vec = GithubIssue::resource_check(Some(source), Some(version));
-    // skip check step requested so we expect size two vec
+    let version_vec = GithubIssue::resource_check(Some(source), Some(version.clone()));
+    // skip check step requested so we expect only the input version
     assert_eq!(
         version_vec,
-        vec![
-            concourse::Version::new(octocrab::models::IssueState::Open),
-            concourse::Version::new(octocrab::models::IssueState::Closed)
-        ],
-        "the resource_check did not return a two size vector of issue states for a requested check skip",
+        vec![version],
+        "the resource_check did not return only the input version for a requested check skip",
     );
 }
 
-#[test]
-#[should_panic(
-    expected = "the check step was unable to return a github issue from the source values"
-)]
-fn test_resource_check_unknown_issue() {
+#[tokio::test]
+async fn test_check_unknown_issue() {
     let source_input = r#"
 {
     "owner": "mitodl",
@@ -119,34 +368,1018 @@ fn test_resource_check_unknown_issue() {
 {
     "state": "open"
 }"#;
-    let source =
-        serde_json::from_str::<<GithubIssue as concourse_resource::Resource>::Source>(source_input)
-            .expect("source could not be deserialized");
+    let source = source(source_input);
     let version = serde_json::from_str::<<GithubIssue as concourse_resource::Resource>::Version>(
         version_input,
     )
     .expect("version could not be deserialized");
-    GithubIssue::resource_check(Some(source), Some(version));
+    // the unfiltered list returns many issues
+    assert_eq!(
+        GithubIssue::check(Some(source), Some(version)).await,
+        Err(StepError::issue(
+            "the check step was unable to return a github issue from the source values",
+            github_issue::IssueError::Config("unexpected number of issues"),
+        )),
+        "the check did not error expectedly for an unfiltered list",
+    );
+}
+
+#[tokio::test]
+async fn test_check_missing_source() {
+    let check = GithubIssue::check(None, None).await;
+    assert_eq!(
+        check,
+        Err(StepError::from(
+            "source is required for the Github Issue resource"
+        )),
+        "the check did not error expectedly for a missing source",
+    );
+    assert_eq!(
+        check.unwrap_err().exit_code(),
+        2,
+        "the check did not return the configuration exit code for a missing source",
+    );
+}
+
+#[tokio::test]
+async fn test_check_comments_invalid_source() {
+    // validate comment trigger source values before any interaction
+    assert_eq!(
+        GithubIssue::check(
+            Some(source(
+                r#"{"owner": "mitodl", "repo": "ol-infrastructure", "comments": true}"#
+            )),
+            None,
+        )
+        .await,
+        Err(StepError::from(
+            "'number' is required in 'source' for comment triggers"
+        )),
+        "the check did not error expectedly for comment triggers without an issue number",
+    );
+    let check = GithubIssue::check(
+        Some(source(
+            r#"{"owner": "mitodl", "repo": "ol-infrastructure", "number": 1, "comments": true, "comment_pattern": "^/approve("}"#,
+        )),
+        None,
+    )
+    .await;
+    assert!(
+        matches!(check, Err(StepError::Config(ref message)) if message.starts_with("the 'comment_pattern' is not a valid regular expression")),
+        "the check did not error expectedly for an invalid comment pattern",
+    );
+}
+
+#[tokio::test]
+async fn test_check_events_invalid_source() {
+    // validate event trigger source values before any interaction
+    assert_eq!(
+        GithubIssue::check(
+            Some(source(
+                r#"{"owner": "mitodl", "repo": "ol-infrastructure", "events": ["labeled"]}"#
+            )),
+            None,
+        )
+        .await,
+        Err(StepError::from(
+            "'number' is required in 'source' for event triggers"
+        )),
+        "the check did not error expectedly for event triggers without an issue number",
+    );
+    assert_eq!(
+        GithubIssue::check(
+            Some(source(
+                r#"{"owner": "mitodl", "repo": "ol-infrastructure", "number": 1, "events": ["labeled", "commented"]}"#
+            )),
+            None,
+        )
+        .await,
+        Err(StepError::Config(String::from(
+            "the event type 'commented' must be one of labeled, unlabeled, assigned, unassigned, milestoned, demilestoned, closed, reopened"
+        ))),
+        "the check did not error expectedly for an invalid event type",
+    );
+}
+
+#[tokio::test]
+async fn test_check_reactions_invalid_source() {
+    // validate reaction trigger source values before any interaction
+    assert_eq!(
+        GithubIssue::check(
+            Some(source(
+                r#"{"owner": "mitodl", "repo": "ol-infrastructure", "reaction": "+1"}"#
+            )),
+            None,
+        )
+        .await,
+        Err(StepError::from(
+            "'number' is required in 'source' for reaction triggers"
+        )),
+        "the check did not error expectedly for reaction triggers without an issue number",
+    );
+    assert_eq!(
+        GithubIssue::check(
+            Some(source(
+                r#"{"owner": "mitodl", "repo": "ol-infrastructure", "number": 1, "reaction": "+1", "reaction_threshold": 0}"#
+            )),
+            None,
+        )
+        .await,
+        Err(StepError::from("the 'reaction_threshold' must be at least 1")),
+        "the check did not error expectedly for a zero reaction threshold",
+    );
+}
+
+#[tokio::test]
+async fn test_check_conditions_invalid_source() {
+    // validate compound trigger conditions before any interaction
+    let check = GithubIssue::check(
+        Some(source(
+            r#"{"owner": "mitodl", "repo": "ol-infrastructure", "number": 1, "conditions": {"state": "closed", "body_pattern": "^approved("}}"#,
+        )),
+        None,
+    )
+    .await;
+    assert!(
+        matches!(check, Err(StepError::Config(ref message)) if message.starts_with("the 'conditions' pattern is not a valid regular expression")),
+        "the check did not error expectedly for an invalid conditions pattern",
+    );
+    assert_eq!(
+        GithubIssue::check(
+            Some(source(
+                r#"{"owner": "mitodl", "repo": "ol-infrastructure", "number": 1, "events": ["labeled"], "conditions": {"state": "open"}}"#,
+            )),
+            None,
+        )
+        .await,
+        Err(StepError::from(
+            "the 'conditions' cannot be combined with 'comments', 'commands', 'events', or 'reaction' in 'source'"
+        )),
+        "the check did not error expectedly for conditions combined with event triggers",
+    );
 }
 
 #[test]
 fn test_resource_in() {
+    // validate basic in reading from mitodl/ol-infrastructure issue 1 and writing to files
+    let source_input = r#"
+{
+    "owner": "mitodl",
+    "repo": "ol-infrastructure",
+    "number": 1
+}"#;
+    let source = source(source_input);
+    // output directory for issue files
+    let output_path = std::env::temp_dir().join("concourse_github_issue_test_resource_in");
+    std::fs::create_dir_all(&output_path).expect("output directory could not be created");
+    let version = concourse::Version::new(
+        octocrab::models::IssueState::Closed,
+        1,
+        "2000-01-01T00:00:00+00:00",
+    );
+    let in_output = GithubIssue::resource_in(
+        Some(source),
+        version.clone(),
+        None,
+        output_path.to_str().unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        in_output.version, version,
+        "the resource in did not return the requested version",
+    );
+    assert_eq!(
+        std::fs::read_to_string(output_path.join("number")).expect("number file was not written"),
+        "1",
+        "the resource in did not write the issue number to a file",
+    );
+    assert_eq!(
+        std::fs::read_to_string(output_path.join("state")).expect("state file was not written"),
+        "closed",
+        "the resource in did not write the issue state to a file",
+    );
+    for file_name in ["issue.json", "title", "body.md", "labels", "url"] {
+        assert!(
+            output_path.join(file_name).is_file(),
+            "the resource in did not write the {file_name} file",
+        );
+    }
+}
+
+#[test]
+fn test_resource_in_missing_source() {
     let in_output = GithubIssue::resource_in(
         None,
-        concourse::Version::new(octocrab::models::IssueState::Open),
+        concourse::Version::new(octocrab::models::IssueState::Open, 1, "today"),
         None,
         "",
+    );
+    assert_eq!(
+        in_output.err().unwrap().to_string(),
+        "source is required for the Github Issue resource",
+        "the resource in did not error expectedly for a missing source",
+    );
+}
+
+#[tokio::test]
+async fn test_out_missing_params() {
+    assert!(
+        GithubIssue::out(None, None, "").await.err()
+            == Some(StepError::from(
+                "source is required for the Github Issue resource"
+            )),
+        "the out did not error expectedly for a missing source",
+    );
+}
+
+#[tokio::test]
+async fn test_out_invalid_params() {
+    // validate invalid params error before any interaction such as the upsert search
+    let source = source(
+        &serde_json::json!({
+            "owner": "mitodl",
+            "repo": "ol-infrastructure",
+            "api_url": "http://127.0.0.1:1"
+        })
+        .to_string(),
+    );
+    let params = serde_json::from_value::<concourse::OutParams>(serde_json::json!({
+        "title": "nightly build failed",
+        "upsert": true,
+        "state_reason": "finished"
+    }))
+    .expect("params could not be deserialized");
+    assert_eq!(
+        GithubIssue::out(Some(source), Some(params), "").await.err(),
+        Some(StepError::issue(
+            "the out/put step params are invalid",
+            github_issue::IssueError::Config(
+                "the issue state reason must be either completed, not_planned, reopened, or duplicate",
+            ),
+        )),
+        "the out did not error expectedly for invalid params before the upsert search",
+    );
+}
+
+#[tokio::test]
+async fn test_upsert_number() {
+    // validate the open issue matching the dedupe key before the title, and excluding pull requests
+    let marker = GithubIssue::dedupe_marker("nightly");
+    let mut pull_request =
+        github_issue::tests::mock_issue(1, "nightly build failed", &format!("fix\n{marker}"));
+    pull_request["pull_request"] = serde_json::json!({
+        "url": "https://api.github.com/repos/octocat/hello-world/pulls/1",
+        "html_url": "https://github.com/octocat/hello-world/pull/1",
+        "diff_url": "https://github.com/octocat/hello-world/pull/1.diff",
+        "patch_url": "https://github.com/octocat/hello-world/pull/1.patch"
+    });
+    let issues = serde_json::json!([
+        pull_request,
+        github_issue::tests::mock_issue(2, "nightly build failed again", ""),
+        github_issue::tests::mock_issue(3, "Nightly build failed", ""),
+        github_issue::tests::mock_issue(4, "nightly build failed", ""),
+        github_issue::tests::mock_issue(5, "flaky tests", &format!("failed\n{marker}")),
+    ])
+    .to_string();
+    let github = github_issue::tests::mock_github(vec![(
+        "/repos/octocat/hello-world/issues?",
+        200,
+        &issues,
+    )])
+    .await;
+    let source = source(
+        &serde_json::json!({
+            "owner": "octocat",
+            "repo": "hello-world",
+            "api_url": github.api_url,
+            "labels": ["ci"],
+            "creator": "octocat"
+        })
+        .to_string(),
+    );
+    let client = GithubIssue::client(&source, None)
+        .await
+        .expect("client for the mock github server was not constructed");
+    assert_eq!(
+        GithubIssue::upsert_number(
+            &client,
+            &source,
+            Some("nightly build failed"),
+            Some("nightly")
+        )
+        .await,
+        Ok(Some(5)),
+        "the issue with the dedupe key did not match before the issue with the title",
+    );
+    assert_eq!(
+        GithubIssue::upsert_number(&client, &source, Some("nightly build failed"), None).await,
+        Ok(Some(4)),
+        "the issue with the exact title did not match",
+    );
+    assert_eq!(
+        GithubIssue::upsert_number(&client, &source, Some("nightly build"), Some("weekly")).await,
+        Ok(None),
+        "an issue matched although neither the dedupe key nor the title matched",
+    );
+    assert_eq!(
+        github.requests()[1],
+        "GET /repos/octocat/hello-world/issues?creator=octocat&state=open&labels=ci",
+        "the open issues were not listed with the source filters",
+    );
+}
+
+#[test]
+fn test_dedupe_body() {
+    // validate hidden dedupe marker appended to body
+    assert_eq!(
+        GithubIssue::dedupe_body(
+            Some("go fix it"),
+            Some("nightly-build"),
+            github_issue::Action::Create
+        ),
+        Some(String::from(
+            "go fix it\n\n<!-- concourse-github-issue dedupe_key: nightly-build -->"
+        )),
+        "the dedupe marker was not appended to the body",
+    );
+    assert_eq!(
+        GithubIssue::dedupe_body(
+            Some("go fix it"),
+            Some("nightly-build"),
+            github_issue::Action::Update
+        ),
+        Some(String::from(
+            "go fix it\n\n<!-- concourse-github-issue dedupe_key: nightly-build -->"
+        )),
+        "the dedupe marker was not appended to the specified body for an update",
+    );
+    assert_eq!(
+        GithubIssue::dedupe_body(None, Some("nightly-build"), github_issue::Action::Create),
+        Some(String::from(
+            "<!-- concourse-github-issue dedupe_key: nightly-build -->"
+        )),
+        "the dedupe marker was not the body for an unspecified body",
+    );
+    assert_eq!(
+        GithubIssue::dedupe_body(None, Some("nightly-build"), github_issue::Action::Update),
+        None,
+        "a body was returned for an update without a specified body",
+    );
+    assert_eq!(
+        GithubIssue::dedupe_body(Some("go fix it"), None, github_issue::Action::Create),
+        Some(String::from("go fix it")),
+        "the body was modified without a dedupe key",
+    );
+    assert_eq!(
+        GithubIssue::dedupe_body(None, None, github_issue::Action::Create),
+        None,
+        "a body was returned without a body or dedupe key",
+    );
+}
+
+#[test]
+fn test_param_or_file() {
+    // validate param values resolved from inline value or file relative to input path
+    let input_path = std::env::temp_dir().join("concourse_github_issue_test_param_or_file");
+    std::fs::create_dir_all(&input_path).expect("input directory could not be created");
+    std::fs::write(input_path.join("body.md"), "# failure report\n")
+        .expect("body file could not be written");
+    let input_path = input_path.to_str().unwrap();
+    assert_eq!(
+        GithubIssue::param_or_file("body", None, Some("body.md"), input_path),
+        Ok(Some(String::from("# failure report\n"))),
+        "the param value was not read from the file",
+    );
+    assert_eq!(
+        GithubIssue::param_or_file("body", Some("go fix it"), None, input_path),
+        Ok(Some(String::from("go fix it"))),
+        "the inline param value was not returned",
+    );
+    assert_eq!(
+        GithubIssue::param_or_file("body", None, None, input_path),
+        Ok(None),
+        "a param value was returned for an unspecified value and file",
+    );
+    assert_eq!(
+        GithubIssue::param_or_file("body", Some("go fix it"), Some("body.md"), input_path),
+        Err(String::from(
+            "only one of the 'body' and 'body_file' params may be specified"
+        )),
+        "specifying both the inline param value and file did not error expectedly",
+    );
+    assert!(
+        GithubIssue::param_or_file("body", None, Some("missing.md"), input_path).is_err(),
+        "a missing param file did not error expectedly",
+    );
+}
+
+#[test]
+fn test_params_number() {
+    // validate issue number resolved from inline param or file relative to input path
+    let input_path = std::env::temp_dir().join("concourse_github_issue_test_params_number");
+    std::fs::create_dir_all(&input_path).expect("input directory could not be created");
+    std::fs::write(input_path.join("number"), "42\n").expect("number file could not be written");
+    std::fs::write(input_path.join("title"), "not a number")
+        .expect("title file could not be written");
+    let input_path = input_path.to_str().unwrap();
+    assert_eq!(
+        GithubIssue::params_number(None, Some("number"), input_path),
+        Ok(Some(42)),
+        "the issue number was not read from the file",
+    );
+    assert_eq!(
+        GithubIssue::params_number(Some(7), None, input_path),
+        Ok(Some(7)),
+        "the inline issue number was not returned",
+    );
+    assert_eq!(
+        GithubIssue::params_number(None, None, input_path),
+        Ok(None),
+        "an issue number was returned for an unspecified number and file",
+    );
+    assert!(
+        GithubIssue::params_number(None, Some("title"), input_path).is_err(),
+        "an invalid issue number file did not error expectedly",
+    );
+}
+
+// octocrab author json with the login
+fn author(login: &str) -> serde_json::Value {
+    serde_json::json!({
+        "login": login,
+        "id": 1,
+        "node_id": "MDQ6VXNlcjE=",
+        "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+        "gravatar_id": "",
+        "url": "https://api.github.com/users/octocat",
+        "html_url": "https://github.com/octocat",
+        "followers_url": "https://api.github.com/users/octocat/followers",
+        "following_url": "https://api.github.com/users/octocat/following{/other_user}",
+        "gists_url": "https://api.github.com/users/octocat/gists{/gist_id}",
+        "starred_url": "https://api.github.com/users/octocat/starred{/owner}{/repo}",
+        "subscriptions_url": "https://api.github.com/users/octocat/subscriptions",
+        "organizations_url": "https://api.github.com/users/octocat/orgs",
+        "repos_url": "https://api.github.com/users/octocat/repos",
+        "events_url": "https://api.github.com/users/octocat/events{/privacy}",
+        "received_events_url": "https://api.github.com/users/octocat/received_events",
+        "type": "User",
+        "site_admin": false
+    })
+}
+
+// creation time which increases with the id
+fn created_at(id: u64) -> String {
+    chrono::DateTime::from_timestamp(1_700_000_000 + id as i64, 0)
+        .expect("creation time out of range")
+        .to_rfc3339()
+}
+
+// octocrab comment with the id, author, and body
+fn comment(id: u64, login: &str, body: &str) -> octocrab::models::issues::Comment {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "node_id": "IC_kwDOA",
+        "url": format!("https://api.github.com/repos/octocat/hello-world/issues/comments/{id}"),
+        "html_url": format!("https://github.com/octocat/hello-world/issues/5#issuecomment-{id}"),
+        "body": body,
+        "user": author(login),
+        "created_at": created_at(id)
+    }))
+    .expect("comment could not be deserialized")
+}
+
+#[test]
+fn test_comment_versions() {
+    // validate comments filtered by cursor and author, and ordered by comment id
+    let comments = vec![
+        comment(3, "octocat", "ship it"),
+        comment(1, "octocat", "first"),
+        comment(2, "hubot", "ship it too"),
+    ];
+    let comment_ids = |new_versions: Vec<(concourse::Version, Option<&str>)>| {
+        new_versions
+            .iter()
+            .map(|(new_version, _)| new_version.comment())
+            .collect::<Vec<Option<u64>>>()
+    };
+    let state = octocrab::models::IssueState::Open;
+    let all_comments = source(r#"{"owner": "octocat", "repo": "hello-world", "comments": true}"#);
+    assert_eq!(
+        comment_ids(GithubIssue::comment_versions(
+            &all_comments,
+            &comments,
+            &state,
+            5,
+            None,
+            None
+        )),
+        vec![Some(1), Some(2), Some(3)],
+        "the comment versions were not ordered by comment id",
+    );
+    assert_eq!(
+        comment_ids(GithubIssue::comment_versions(
+            &all_comments,
+            &comments,
+            &state,
+            5,
+            None,
+            Some(2)
+        )),
+        vec![Some(3)],
+        "the comment versions were not after the comment id cursor",
+    );
+    assert_eq!(
+        comment_ids(GithubIssue::comment_versions(
+            &source(
+                r#"{"owner": "octocat", "repo": "hello-world", "comments": true, "comment_author": "OctoCat"}"#
+            ),
+            &comments,
+            &state,
+            5,
+            None,
+            None
+        )),
+        vec![Some(1), Some(3)],
+        "the comment versions were not filtered by the comment author",
+    );
+    assert_eq!(
+        comment_ids(GithubIssue::comment_versions(
+            &all_comments,
+            &comments,
+            &state,
+            5,
+            Some(&regex::Regex::new("^ship").unwrap()),
+            None
+        )),
+        vec![Some(2), Some(3)],
+        "the comment versions were not filtered by the comment pattern",
+    );
+    // validate only allowed slash commands are converted with their authors
+    let comments = vec![
+        comment(1, "octocat", "/deploy production"),
+        comment(2, "hubot", "/destroy production"),
+        comment(3, "hubot", "deploy it"),
+    ];
+    let new_versions = GithubIssue::comment_versions(
+        &source(r#"{"owner": "octocat", "repo": "hello-world", "commands": ["deploy"]}"#),
+        &comments,
+        &state,
+        5,
+        None,
+        None,
+    );
+    assert_eq!(
+        new_versions.len(),
+        1,
+        "the comment versions were not only for allowed slash commands",
+    );
+    assert_eq!(
+        (
+            new_versions[0].0.command(),
+            new_versions[0].0.arguments(),
+            new_versions[0].1
+        ),
+        (Some("deploy"), Some("production"), Some("octocat")),
+        "the slash command version did not contain the command, arguments, and author",
+    );
+}
+
+#[test]
+fn test_writer_versions() {
+    // validate slash commands from users without write permission are dropped
+    let version = |comment| {
+        concourse::Version::new(octocrab::models::IssueState::Open, 5, created_at(comment))
+            .with_comment(comment)
+    };
+    let new_versions = vec![
+        (version(1), Some("octocat")),
+        (version(2), Some("hubot")),
+        (version(3), None),
+    ];
+    assert_eq!(
+        GithubIssue::writer_versions(new_versions, &[String::from("octocat")]),
+        vec![version(1), version(3)],
+        "the slash command versions from users without write permission were not dropped",
+    );
+}
+
+#[test]
+fn test_cursor_versions() {
+    // validate the input version and new versions after its cursor, or only the latest new version for the first check
+    let version = |event| {
+        concourse::Version::new(octocrab::models::IssueState::Open, 5, created_at(event))
+            .with_event(event, "labeled", None)
+    };
+    assert_eq!(
+        GithubIssue::cursor_versions(None, false, vec![version(2), version(3)]),
+        vec![version(3)],
+        "the first check did not return only the latest new version",
+    );
+    assert_eq!(
+        GithubIssue::cursor_versions(None, false, Vec::new()),
+        Vec::new(),
+        "the first check returned a version without new versions",
+    );
+    assert_eq!(
+        GithubIssue::cursor_versions(Some(version(1)), true, vec![version(2), version(3)]),
+        vec![version(1), version(2), version(3)],
+        "the check did not return the input version and all new versions",
+    );
+    assert_eq!(
+        GithubIssue::cursor_versions(Some(version(1)), true, Vec::new()),
+        vec![version(1)],
+        "the check did not return only the input version without new versions",
+    );
+}
+
+#[test]
+fn test_updated_versions() {
+    // validate the input version and new versions ordered by last updated time, or only the latest new version for the first check
+    let version = |number, updated| {
+        concourse::Version::new(
+            octocrab::models::IssueState::Open,
+            number,
+            created_at(updated),
+        )
+    };
+    let new_versions = || vec![version(3, 30), version(1, 10), version(2, 20)];
+    assert_eq!(
+        GithubIssue::updated_versions(None, new_versions()),
+        vec![version(3, 30)],
+        "the first check did not return only the latest updated version",
+    );
+    assert_eq!(
+        GithubIssue::updated_versions(None, Vec::new()),
+        Vec::new(),
+        "the first check returned a version without new versions",
+    );
+    // the listed input version is not repeated, and earlier updates are dropped
+    assert_eq!(
+        GithubIssue::updated_versions(Some(version(2, 20)), new_versions()),
+        vec![version(2, 20), version(3, 30)],
+        "the check did not return the input version and the later updated versions in order",
+    );
+    // another issue updated at the same time as the input version is retained
+    assert_eq!(
+        GithubIssue::updated_versions(Some(version(2, 20)), vec![version(4, 20), version(2, 20)]),
+        vec![version(2, 20), version(4, 20)],
+        "the check did not return another issue updated at the input version time",
+    );
+    assert_eq!(
+        GithubIssue::updated_versions(Some(version(2, 20)), Vec::new()),
+        vec![version(2, 20)],
+        "the check did not return only the input version without new versions",
+    );
+}
+
+#[test]
+fn test_event_versions() {
+    // validate events filtered by type, label, and cursor, and ordered by event id
+    let event = |id, event: &str, label: Option<&str>| github_issue::IssueEvent {
+        id,
+        event: String::from(event),
+        actor: Some(github_issue::EventActor {
+            login: String::from("octocat"),
+        }),
+        created_at: created_at(id).parse().unwrap(),
+        label: label.map(|name| github_issue::EventLabel {
+            name: String::from(name),
+        }),
+    };
+    let events = || {
+        vec![
+            event(5, "unlabeled", Some("approved")),
+            event(1, "closed", None),
+            event(2, "labeled", Some("approved")),
+            event(3, "labeled", Some("blocked")),
+            event(4, "labeled", Some("Approved")),
+        ]
+    };
+    let event_ids = |new_versions: Vec<concourse::Version>| {
+        new_versions
+            .iter()
+            .map(concourse::Version::event)
+            .collect::<Vec<Option<u64>>>()
+    };
+    let state = octocrab::models::IssueState::Open;
+    let approved = source(
+        r#"{"owner": "octocat", "repo": "hello-world", "events": ["labeled", "unlabeled"], "event_label": "approved"}"#,
+    );
+    assert_eq!(
+        event_ids(GithubIssue::event_versions(
+            &approved,
+            events(),
+            &state,
+            5,
+            None
+        )),
+        vec![Some(2), Some(4), Some(5)],
+        "the event versions were not filtered by type and label, and ordered by event id",
+    );
+    assert_eq!(
+        event_ids(GithubIssue::event_versions(
+            &approved,
+            events(),
+            &state,
+            5,
+            Some(4)
+        )),
+        vec![Some(5)],
+        "the event versions were not after the event id cursor",
+    );
+    assert_eq!(
+        event_ids(GithubIssue::event_versions(
+            &source(r#"{"owner": "octocat", "repo": "hello-world", "events": ["closed"]}"#),
+            events(),
+            &state,
+            5,
+            None
+        )),
+        vec![Some(1)],
+        "the event versions were not filtered by type",
+    );
+}
+
+#[test]
+fn test_reaction_versions() {
+    // validate the version when the reaction count reaches the threshold
+    let reaction = |id: u64, login: &str, content: &str| {
+        serde_json::from_value::<octocrab::models::reactions::Reaction>(serde_json::json!({
+            "id": id,
+            "node_id": "REA_lATO",
+            "user": author(login),
+            "content": content,
+            "created_at": created_at(id)
+        }))
+        .expect("reaction could not be deserialized")
+    };
+    let reactions = || {
+        vec![
+            reaction(4, "mona", "+1"),
+            reaction(1, "octocat", "+1"),
+            reaction(2, "hubot", "heart"),
+            reaction(3, "hubot", "+1"),
+        ]
+    };
+    let state = octocrab::models::IssueState::Open;
+    let threshold = source(
+        r#"{"owner": "octocat", "repo": "hello-world", "number": 5, "reaction": "+1", "reaction_threshold": 2}"#,
+    );
+    let new_version =
+        concourse::Version::new(state.clone(), 5, created_at(3)).with_reactions(Some(3), 3);
+    assert_eq!(
+        GithubIssue::reaction_versions(&threshold, reactions(), None, &state, 5, None),
+        vec![new_version.clone()],
+        "the first check did not return the version for the reaction which reached the threshold",
+    );
+    // the crossing is stable for an input version at or above the threshold e.g. after an earlier reaction is removed
+    let version =
+        concourse::Version::new(state.clone(), 5, created_at(1)).with_reactions(Some(1), 2);
+    assert_eq!(
+        GithubIssue::reaction_versions(
+            &threshold,
+            reactions(),
+            None,
+            &state,
+            5,
+            Some(version.clone())
+        ),
+        vec![version],
+        "the check returned a new version although the threshold was already reached",
+    );
+    let version =
+        concourse::Version::new(state.clone(), 5, created_at(1)).with_reactions(Some(1), 1);
+    assert_eq!(
+        GithubIssue::reaction_versions(
+            &threshold,
+            reactions(),
+            None,
+            &state,
+            5,
+            Some(version.clone())
+        ),
+        vec![version, new_version.clone()],
+        "the check did not return a new version for an input version below the threshold",
+    );
+    // the count dropping below the threshold is recorded with a version without the threshold reaction...
+    let below = || vec![reaction(1, "octocat", "+1"), reaction(2, "hubot", "heart")];
+    let reset_versions = GithubIssue::reaction_versions(
+        &threshold,
+        below(),
+        None,
+        &state,
+        5,
+        Some(new_version.clone()),
+    );
+    assert_eq!(
+        reset_versions.len(),
+        2,
+        "the check did not return a new version for the count below the threshold",
+    );
+    assert_eq!(
+        (
+            &reset_versions[0],
+            reset_versions[1].number(),
+            reset_versions[1].reaction(),
+            reset_versions[1].reactions()
+        ),
+        (&new_version, Some(5), None, Some(1)),
+        "the new version for the count below the threshold did not contain only the count",
+    );
+    // ...so that reaching the threshold again produces a new version
+    let reset = reset_versions[1].clone();
+    assert_eq!(
+        GithubIssue::reaction_versions(
+            &threshold,
+            reactions(),
+            None,
+            &state,
+            5,
+            Some(reset.clone())
+        ),
+        vec![reset.clone(), new_version],
+        "the check did not return a new version for reaching the threshold again",
+    );
+    assert_eq!(
+        GithubIssue::reaction_versions(&threshold, below(), None, &state, 5, Some(reset.clone())),
+        vec![reset],
+        "the check returned a new version although the count remained below the threshold",
+    );
+    assert_eq!(
+        GithubIssue::reaction_versions(
+            &threshold,
+            reactions(),
+            Some(&[String::from("OctoCat")]),
+            &state,
+            5,
+            None
+        ),
+        Vec::new(),
+        "the reactions were not filtered by the team members",
+    );
+}
+
+#[test]
+fn test_write_reaction_files() {
+    // validate reaction count and threshold reaction id written to files only for reaction versions
+    let output_path = std::env::temp_dir().join("concourse_github_issue_test_write_reaction_files");
+    let _ = std::fs::remove_dir_all(&output_path);
+    std::fs::create_dir_all(&output_path).expect("output directory could not be created");
+    let version = concourse::Version::new(
+        octocrab::models::IssueState::Open,
+        5,
+        "2000-01-01T00:00:00+00:00",
+    );
+    GithubIssue::write_reaction_files(&version, output_path.to_str().unwrap())
+        .expect("the version without reactions errored");
+    assert!(
+        !output_path.join("reactions").exists(),
+        "the reactions file was written for a version without reactions",
+    );
+    GithubIssue::write_reaction_files(
+        &version.clone().with_reactions(None, 1),
+        output_path.to_str().unwrap(),
     )
-    .unwrap();
+    .expect("the reaction files could not be written");
+    assert_eq!(
+        (
+            std::fs::read_to_string(output_path.join("reactions")).ok(),
+            output_path.join("reaction").exists()
+        ),
+        (Some(String::from("1")), false),
+        "only the reactions file was not written for a count below the threshold",
+    );
+    GithubIssue::write_reaction_files(
+        &version.with_reactions(Some(13579), 3),
+        output_path.to_str().unwrap(),
+    )
+    .expect("the reaction files could not be written");
+    assert_eq!(
+        std::fs::read_to_string(output_path.join("reaction"))
+            .expect("reaction file was not written"),
+        "13579",
+        "the threshold reaction id was not written to a file",
+    );
+}
+
+#[test]
+fn test_parse_command() {
+    // validate slash command and arguments parsed from the first line of allowed commands
+    let commands = vec![String::from("deploy"), String::from("/rollback")];
+    assert_eq!(
+        GithubIssue::parse_command("/deploy staging", &commands),
+        Some((String::from("deploy"), String::from("staging"))),
+        "the command and its arguments were not parsed",
+    );
+    assert_eq!(
+        GithubIssue::parse_command("\n/rollback  v1.2.3 --force \r\nthanks", &commands),
+        Some((String::from("rollback"), String::from("v1.2.3 --force"))),
+        "the command and its arguments were not parsed from the first line",
+    );
+    assert_eq!(
+        GithubIssue::parse_command("/deploy", &commands),
+        Some((String::from("deploy"), String::new())),
+        "the command without arguments was not parsed",
+    );
+    assert_eq!(
+        GithubIssue::parse_command("/destroy production", &commands),
+        None,
+        "a command which is not allowed was parsed",
+    );
     assert_eq!(
-        in_output.version,
-        concourse::Version::new(octocrab::models::IssueState::Open),
-        "the resource in did not dummy the expected return version output",
+        GithubIssue::parse_command("please /deploy staging", &commands),
+        None,
+        "a command not at the beginning of the comment was parsed",
     );
 }
 
 #[test]
-#[should_panic(expected = "source is required for the Github Issue resource")]
-fn test_resource_in_missing_params() {
-    GithubIssue::resource_out(None, None, "");
+fn test_write_command_files() {
+    // validate command and arguments written to files only for command versions
+    let output_path = std::env::temp_dir().join("concourse_github_issue_test_write_command_files");
+    std::fs::create_dir_all(&output_path).expect("output directory could not be created");
+    let version = concourse::Version::new(
+        octocrab::models::IssueState::Open,
+        1,
+        "2000-01-01T00:00:00+00:00",
+    );
+    let _ = std::fs::remove_file(output_path.join("command"));
+    GithubIssue::write_command_files(&version, output_path.to_str().unwrap())
+        .expect("the version without a command errored");
+    assert!(
+        !output_path.join("command").exists(),
+        "the command file was written for a version without a command",
+    );
+    GithubIssue::write_command_files(
+        &version
+            .with_comment(12345)
+            .with_command("deploy", "staging"),
+        output_path.to_str().unwrap(),
+    )
+    .expect("the command files could not be written");
+    assert_eq!(
+        std::fs::read_to_string(output_path.join("command")).expect("command file was not written"),
+        "deploy",
+        "the command was not written to a file",
+    );
+    assert_eq!(
+        std::fs::read_to_string(output_path.join("arguments"))
+            .expect("arguments file was not written"),
+        "staging",
+        "the arguments were not written to a file",
+    );
+}
+
+#[test]
+fn test_build_url() {
+    // validate build url construction from concourse build metadata variables
+    let mut variables = vec![
+        ("BUILD_ID", String::from("1234")),
+        ("BUILD_NAME", String::from("42")),
+        ("BUILD_JOB_NAME", String::from("deploy")),
+        ("BUILD_PIPELINE_NAME", String::from("release")),
+        ("BUILD_TEAM_NAME", String::from("main")),
+        ("ATC_EXTERNAL_URL", String::from("https://ci.example.com/")),
+    ];
+    assert_eq!(
+        GithubIssue::build_url(&variables),
+        Some(String::from(
+            "https://ci.example.com/teams/main/pipelines/release/jobs/deploy/builds/42"
+        )),
+        "the build url was not constructed for a pipeline job build",
+    );
+    variables.push((
+        "BUILD_PIPELINE_INSTANCE_VARS",
+        String::from("{\"env\":\"dev\"}"),
+    ));
+    assert_eq!(
+        GithubIssue::build_url(&variables),
+        Some(String::from("https://ci.example.com/builds/1234")),
+        "the build url was not constructed for a pipeline instance build",
+    );
+    assert_eq!(
+        GithubIssue::build_url(&[("BUILD_ID", String::from("1234"))]),
+        None,
+        "a build url was constructed without the external url",
+    );
+}
+
+#[test]
+fn test_expand_variables() {
+    // validate expansion of known variables and retention of unknown variables
+    let variables = vec![
+        ("BUILD_PIPELINE_NAME", String::from("release")),
+        (
+            "BUILD_URL",
+            String::from("https://ci.example.com/builds/1234"),
+        ),
+    ];
+    assert_eq!(
+        GithubIssue::expand_variables(
+            "${BUILD_PIPELINE_NAME} failed at ${BUILD_URL} in ${BUILD_TEAM_NAME}",
+            &variables
+        ),
+        "release failed at https://ci.example.com/builds/1234 in ${BUILD_TEAM_NAME}",
+        "the variables were not expanded expectedly",
+    );
 }